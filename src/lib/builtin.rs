@@ -0,0 +1,825 @@
+use std::{any::Any, fmt::Display};
+
+use crate::data_type::DataType;
+
+#[derive(Clone, Copy)]
+pub enum Parameter {
+    Number,
+    Int,
+    Char,
+    // A STRING, or a CHAR used as a STRING of one character.
+    Text,
+    // Any INT, FLOAT, CHAR, BOOL or STRING value.
+    Scalar,
+}
+
+pub enum Returns {
+    Type(DataType),
+    // INT when every argument is an INT, otherwise FLOAT like `Binary::visit`.
+    Numeric,
+    // The data type of the first argument.
+    First,
+}
+
+pub type Function = fn(&[Box<dyn Any>]) -> Result<Box<dyn Any>, String>;
+
+pub struct Builtin {
+    pub name: &'static str,
+    pub parameters: &'static [Parameter],
+    pub optional: usize,
+    pub variadic: bool,
+    pub returns: Returns,
+    pub call: Function,
+}
+
+static BUILTINS: &[Builtin] = &[
+    Builtin {
+        name: "ABS",
+        parameters: &[Parameter::Number],
+        optional: 0,
+        variadic: false,
+        returns: Returns::Numeric,
+        call: abs,
+    },
+    Builtin {
+        name: "SQRT",
+        parameters: &[Parameter::Number],
+        optional: 0,
+        variadic: false,
+        returns: Returns::Type(DataType::FLOAT),
+        call: sqrt,
+    },
+    Builtin {
+        name: "POW",
+        parameters: &[Parameter::Number, Parameter::Number],
+        optional: 0,
+        variadic: false,
+        returns: Returns::Type(DataType::FLOAT),
+        call: pow,
+    },
+    Builtin {
+        name: "FLOOR",
+        parameters: &[Parameter::Number],
+        optional: 0,
+        variadic: false,
+        returns: Returns::Numeric,
+        call: |arguments| round_with(&arguments[0], f64::floor),
+    },
+    Builtin {
+        name: "CEIL",
+        parameters: &[Parameter::Number],
+        optional: 0,
+        variadic: false,
+        returns: Returns::Numeric,
+        call: |arguments| round_with(&arguments[0], f64::ceil),
+    },
+    Builtin {
+        name: "ROUND",
+        parameters: &[Parameter::Number, Parameter::Int],
+        optional: 1,
+        variadic: false,
+        returns: Returns::Numeric,
+        call: round,
+    },
+    Builtin {
+        name: "MIN",
+        parameters: &[Parameter::Number, Parameter::Number],
+        optional: 0,
+        variadic: true,
+        returns: Returns::Numeric,
+        call: |arguments| extreme(arguments, |value, other| value < other),
+    },
+    Builtin {
+        name: "MAX",
+        parameters: &[Parameter::Number, Parameter::Number],
+        optional: 0,
+        variadic: true,
+        returns: Returns::Numeric,
+        call: |arguments| extreme(arguments, |value, other| value > other),
+    },
+    Builtin {
+        name: "SIN",
+        parameters: &[Parameter::Number],
+        optional: 0,
+        variadic: false,
+        returns: Returns::Type(DataType::FLOAT),
+        call: |arguments| Ok(Box::new(number(&arguments[0]).sin())),
+    },
+    Builtin {
+        name: "COS",
+        parameters: &[Parameter::Number],
+        optional: 0,
+        variadic: false,
+        returns: Returns::Type(DataType::FLOAT),
+        call: |arguments| Ok(Box::new(number(&arguments[0]).cos())),
+    },
+    Builtin {
+        name: "TAN",
+        parameters: &[Parameter::Number],
+        optional: 0,
+        variadic: false,
+        returns: Returns::Type(DataType::FLOAT),
+        call: |arguments| Ok(Box::new(number(&arguments[0]).tan())),
+    },
+    Builtin {
+        name: "MOD",
+        parameters: &[Parameter::Number, Parameter::Number],
+        optional: 0,
+        variadic: false,
+        returns: Returns::Numeric,
+        call: modulo,
+    },
+    Builtin {
+        name: "LEN",
+        parameters: &[Parameter::Text],
+        optional: 0,
+        variadic: false,
+        returns: Returns::Type(DataType::INT),
+        call: length,
+    },
+    Builtin {
+        name: "SUBSTR",
+        parameters: &[Parameter::Text, Parameter::Int, Parameter::Int],
+        optional: 1,
+        variadic: false,
+        returns: Returns::Type(DataType::STR),
+        call: substring,
+    },
+    Builtin {
+        name: "UPPER",
+        parameters: &[Parameter::Text],
+        optional: 0,
+        variadic: false,
+        returns: Returns::First,
+        call: |arguments| change_case(&arguments[0], char::to_uppercase, str::to_uppercase),
+    },
+    Builtin {
+        name: "LOWER",
+        parameters: &[Parameter::Text],
+        optional: 0,
+        variadic: false,
+        returns: Returns::First,
+        call: |arguments| change_case(&arguments[0], char::to_lowercase, str::to_lowercase),
+    },
+    Builtin {
+        name: "TRIM",
+        parameters: &[Parameter::Text],
+        optional: 0,
+        variadic: false,
+        returns: Returns::Type(DataType::STR),
+        call: |arguments| Ok(Box::new(text(&arguments[0]).trim().to_owned())),
+    },
+    Builtin {
+        name: "INDEXOF",
+        parameters: &[Parameter::Text, Parameter::Text, Parameter::Int],
+        optional: 1,
+        variadic: false,
+        returns: Returns::Type(DataType::INT),
+        call: index_of,
+    },
+    Builtin {
+        name: "CHARAT",
+        parameters: &[Parameter::Text, Parameter::Int],
+        optional: 0,
+        variadic: false,
+        returns: Returns::Type(DataType::CHAR),
+        call: char_at,
+    },
+    Builtin {
+        name: "ISDIGIT",
+        parameters: &[Parameter::Char],
+        optional: 0,
+        variadic: false,
+        returns: Returns::Type(DataType::BOOL),
+        call: |arguments| classify(&arguments[0], |character| character.is_ascii_digit()),
+    },
+    Builtin {
+        name: "ISALPHA",
+        parameters: &[Parameter::Char],
+        optional: 0,
+        variadic: false,
+        returns: Returns::Type(DataType::BOOL),
+        call: |arguments| classify(&arguments[0], char::is_alphabetic),
+    },
+    Builtin {
+        name: "ISSPACE",
+        parameters: &[Parameter::Char],
+        optional: 0,
+        variadic: false,
+        returns: Returns::Type(DataType::BOOL),
+        call: |arguments| classify(&arguments[0], char::is_whitespace),
+    },
+    Builtin {
+        name: "ISUPPER",
+        parameters: &[Parameter::Char],
+        optional: 0,
+        variadic: false,
+        returns: Returns::Type(DataType::BOOL),
+        call: |arguments| classify(&arguments[0], char::is_uppercase),
+    },
+    Builtin {
+        name: "ISLOWER",
+        parameters: &[Parameter::Char],
+        optional: 0,
+        variadic: false,
+        returns: Returns::Type(DataType::BOOL),
+        call: |arguments| classify(&arguments[0], char::is_lowercase),
+    },
+    Builtin {
+        name: "TOINT",
+        parameters: &[Parameter::Scalar],
+        optional: 0,
+        variadic: false,
+        returns: Returns::Type(DataType::INT),
+        call: to_int,
+    },
+    Builtin {
+        name: "TOFLOAT",
+        parameters: &[Parameter::Scalar],
+        optional: 0,
+        variadic: false,
+        returns: Returns::Type(DataType::FLOAT),
+        call: to_float,
+    },
+    Builtin {
+        name: "TOCHAR",
+        parameters: &[Parameter::Scalar],
+        optional: 0,
+        variadic: false,
+        returns: Returns::Type(DataType::CHAR),
+        call: to_char,
+    },
+    Builtin {
+        name: "TOBOOL",
+        parameters: &[Parameter::Scalar],
+        optional: 0,
+        variadic: false,
+        returns: Returns::Type(DataType::BOOL),
+        call: to_bool,
+    },
+    Builtin {
+        name: "TOSTR",
+        parameters: &[Parameter::Scalar],
+        optional: 0,
+        variadic: false,
+        returns: Returns::Type(DataType::STR),
+        call: |arguments| Ok(Box::new(DataType::stringify_primitives(&arguments[0])?)),
+    },
+    Builtin {
+        name: "ORD",
+        parameters: &[Parameter::Char],
+        optional: 0,
+        variadic: false,
+        returns: Returns::Type(DataType::INT),
+        call: |arguments| {
+            Ok(Box::new(
+                *DataType::downcast_box_any::<char>(&arguments[0]).unwrap() as i32,
+            ))
+        },
+    },
+    Builtin {
+        name: "CHR",
+        parameters: &[Parameter::Int],
+        optional: 0,
+        variadic: false,
+        returns: Returns::Type(DataType::CHAR),
+        call: chr,
+    },
+    Builtin {
+        name: "FORMAT",
+        parameters: &[Parameter::Text, Parameter::Scalar],
+        optional: 1,
+        variadic: true,
+        returns: Returns::Type(DataType::STR),
+        call: format_text,
+    },
+];
+
+pub fn get(name: &str) -> Option<&'static Builtin> {
+    return BUILTINS.iter().find(|builtin| builtin.name == name);
+}
+
+impl Parameter {
+    pub fn accepts(&self, data_type: &DataType) -> bool {
+        return match self {
+            Parameter::Number => matches!(data_type, DataType::INT | DataType::FLOAT),
+            Parameter::Int => *data_type == DataType::INT,
+            Parameter::Char => *data_type == DataType::CHAR,
+            Parameter::Text => matches!(data_type, DataType::STR | DataType::CHAR),
+            Parameter::Scalar => !matches!(data_type, DataType::ARRAY(..) | DataType::RECORD(..)),
+        };
+    }
+}
+
+impl Display for Parameter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Parameter::Number => write!(f, "INT or FLOAT"),
+            Parameter::Int => write!(f, "INT"),
+            Parameter::Char => write!(f, "CHAR"),
+            Parameter::Text => write!(f, "STRING or CHAR"),
+            Parameter::Scalar => write!(f, "INT, FLOAT, CHAR, BOOL or STRING"),
+        }
+    }
+}
+
+impl Builtin {
+    pub fn min_arguments(&self) -> usize {
+        return self.parameters.len() - self.optional;
+    }
+
+    pub fn accepts_arguments(&self, count: usize) -> bool {
+        return count >= self.min_arguments() && (self.variadic || count <= self.parameters.len());
+    }
+
+    pub fn parameter(&self, index: usize) -> Parameter {
+        return self.parameters[index.min(self.parameters.len() - 1)];
+    }
+
+    pub fn return_type(&self, arguments: &[Option<DataType>]) -> Option<DataType> {
+        return match &self.returns {
+            Returns::Type(data_type) => Some(data_type.clone()),
+            Returns::Numeric => {
+                let mut result = DataType::INT;
+                for argument in arguments
+                    .iter()
+                    .take(self.numeric_arguments(arguments.len()))
+                {
+                    match argument {
+                        Some(DataType::INT) => (),
+                        Some(_) => result = DataType::FLOAT,
+                        None => return None,
+                    }
+                }
+                Some(result)
+            }
+            Returns::First => arguments.first()?.clone(),
+        };
+    }
+
+    // ROUND takes its number of decimal places as an INT that does not
+    // affect the result type.
+    fn numeric_arguments(&self, count: usize) -> usize {
+        return if self.variadic {
+            count
+        } else {
+            count.min(self.parameters.len() - self.optional)
+        };
+    }
+}
+
+fn is_int(value: &Box<dyn Any>) -> bool {
+    return DataType::downcast_box_any::<i32>(value).is_some();
+}
+
+fn number(value: &Box<dyn Any>) -> f64 {
+    if let Some(value) = DataType::downcast_box_any::<i32>(value) {
+        return f64::from(*value);
+    }
+    return *DataType::downcast_box_any::<f64>(value).unwrap();
+}
+
+fn abs(arguments: &[Box<dyn Any>]) -> Result<Box<dyn Any>, String> {
+    if let Some(value) = DataType::downcast_box_any::<i32>(&arguments[0]) {
+        return match value.checked_abs() {
+            Some(value) => Ok(Box::new(value)),
+            None => Err("INT overflow.".to_owned()),
+        };
+    }
+    return Ok(Box::new(number(&arguments[0]).abs()));
+}
+
+fn sqrt(arguments: &[Box<dyn Any>]) -> Result<Box<dyn Any>, String> {
+    let value = number(&arguments[0]);
+    if value < 0.0 {
+        return Err("Cannot take the square root of a negative number.".to_owned());
+    }
+    return Ok(Box::new(value.sqrt()));
+}
+
+fn pow(arguments: &[Box<dyn Any>]) -> Result<Box<dyn Any>, String> {
+    let value = number(&arguments[0]).powf(number(&arguments[1]));
+    if value.is_nan() {
+        return Err("The result is not a real number.".to_owned());
+    }
+    return Ok(Box::new(value));
+}
+
+fn round_with(value: &Box<dyn Any>, round: fn(f64) -> f64) -> Result<Box<dyn Any>, String> {
+    if let Some(value) = DataType::downcast_box_any::<i32>(value) {
+        return Ok(Box::new(*value));
+    }
+    return Ok(Box::new(round(number(value))));
+}
+
+fn round(arguments: &[Box<dyn Any>]) -> Result<Box<dyn Any>, String> {
+    let places = match arguments.get(1) {
+        Some(places) => *DataType::downcast_box_any::<i32>(places).unwrap(),
+        None => 0,
+    };
+    if places < 0 {
+        return Err("Expected a non-negative number of decimal places.".to_owned());
+    }
+    if is_int(&arguments[0]) {
+        return round_with(&arguments[0], f64::round);
+    }
+    let scale = 10f64.powi(places);
+    return Ok(Box::new((number(&arguments[0]) * scale).round() / scale));
+}
+
+fn extreme(
+    arguments: &[Box<dyn Any>],
+    is_better: fn(f64, f64) -> bool,
+) -> Result<Box<dyn Any>, String> {
+    let mut best = number(&arguments[0]);
+    for argument in &arguments[1..] {
+        let value = number(argument);
+        if is_better(value, best) {
+            best = value;
+        }
+    }
+    if arguments.iter().all(is_int) {
+        return Ok(Box::new(best as i32));
+    }
+    return Ok(Box::new(best));
+}
+
+fn modulo(arguments: &[Box<dyn Any>]) -> Result<Box<dyn Any>, String> {
+    if let (Some(left), Some(right)) = (
+        DataType::downcast_box_any::<i32>(&arguments[0]),
+        DataType::downcast_box_any::<i32>(&arguments[1]),
+    ) {
+        return match left.checked_rem(*right) {
+            Some(value) => Ok(Box::new(value)),
+            None if *right == 0 => Err("Division by zero.".to_owned()),
+            None => Err("INT overflow.".to_owned()),
+        };
+    }
+    let right = number(&arguments[1]);
+    if right == 0.0 {
+        return Err("Division by zero.".to_owned());
+    }
+    return Ok(Box::new(number(&arguments[0]) % right));
+}
+
+fn text(value: &Box<dyn Any>) -> String {
+    if let Some(value) = DataType::downcast_box_any::<char>(value) {
+        return value.to_string();
+    }
+    return DataType::downcast_box_any::<String>(value).unwrap().clone();
+}
+
+fn int(value: &Box<dyn Any>) -> i32 {
+    return *DataType::downcast_box_any::<i32>(value).unwrap();
+}
+
+fn position(index: i32, length: usize, allow_end: bool) -> Result<usize, String> {
+    let limit = if allow_end { length + 1 } else { length };
+    if index < 0 || index as usize >= limit {
+        return Err(format!(
+            "Index {} is out of bounds for a text of length {}.",
+            index, length
+        ));
+    }
+    return Ok(index as usize);
+}
+
+fn length(arguments: &[Box<dyn Any>]) -> Result<Box<dyn Any>, String> {
+    return match i32::try_from(text(&arguments[0]).chars().count()) {
+        Ok(length) => Ok(Box::new(length)),
+        Err(_) => Err("INT overflow.".to_owned()),
+    };
+}
+
+fn substring(arguments: &[Box<dyn Any>]) -> Result<Box<dyn Any>, String> {
+    let characters: Vec<char> = text(&arguments[0]).chars().collect();
+    let start = position(int(&arguments[1]), characters.len(), true)?;
+    let count = match arguments.get(2).map(int) {
+        Some(count) if count < 0 => {
+            return Err("Expected a non-negative length.".to_owned());
+        }
+        Some(count) => count as usize,
+        None => characters.len(),
+    };
+    let end = characters.len().min(start.saturating_add(count));
+    return Ok(Box::new(characters[start..end].iter().collect::<String>()));
+}
+
+fn change_case<T: Iterator<Item = char>>(
+    value: &Box<dyn Any>,
+    change_char: fn(char) -> T,
+    change_str: fn(&str) -> String,
+) -> Result<Box<dyn Any>, String> {
+    if let Some(value) = DataType::downcast_box_any::<char>(value) {
+        return Ok(Box::new(change_char(*value).next().unwrap_or(*value)));
+    }
+    return Ok(Box::new(change_str(&text(value))));
+}
+
+fn index_of(arguments: &[Box<dyn Any>]) -> Result<Box<dyn Any>, String> {
+    let characters: Vec<char> = text(&arguments[0]).chars().collect();
+    let search: Vec<char> = text(&arguments[1]).chars().collect();
+    let start = match arguments.get(2) {
+        Some(start) => position(int(start), characters.len(), true)?,
+        None => 0,
+    };
+    let found = (start..=characters.len())
+        .find(|index| characters[*index..].starts_with(&search))
+        .map_or(-1, |index| index as i32);
+    return Ok(Box::new(found));
+}
+
+fn char_at(arguments: &[Box<dyn Any>]) -> Result<Box<dyn Any>, String> {
+    let characters: Vec<char> = text(&arguments[0]).chars().collect();
+    let index = position(int(&arguments[1]), characters.len(), false)?;
+    return Ok(Box::new(characters[index]));
+}
+
+fn classify(value: &Box<dyn Any>, is_class: fn(char) -> bool) -> Result<Box<dyn Any>, String> {
+    return Ok(Box::new(is_class(
+        *DataType::downcast_box_any::<char>(value).unwrap(),
+    )));
+}
+
+fn cannot_convert(value: &Box<dyn Any>, data_type: DataType) -> String {
+    return format!(
+        "Cannot convert {} '{}' to {}.",
+        DataType::box_any_to_data_type(value).unwrap(),
+        DataType::stringify_primitives(value).unwrap_or_default(),
+        data_type
+    );
+}
+
+fn digit(value: &Box<dyn Any>, data_type: DataType) -> Result<u32, String> {
+    let character = *DataType::downcast_box_any::<char>(value).unwrap();
+    return match character.to_digit(10) {
+        Some(digit) => Ok(digit),
+        None => Err(cannot_convert(value, data_type)),
+    };
+}
+
+fn parse(value: &Box<dyn Any>, data_type: DataType) -> Result<Box<dyn Any>, String> {
+    return DataType::parse_literal(&text(value), &data_type)
+        .map_err(|error| format!("{} {}", cannot_convert(value, data_type), error));
+}
+
+fn to_int(arguments: &[Box<dyn Any>]) -> Result<Box<dyn Any>, String> {
+    let value = &arguments[0];
+    return match DataType::box_any_to_data_type(value).unwrap() {
+        DataType::INT => Ok(Box::new(int(value))),
+        DataType::FLOAT => {
+            let float = number(value).trunc();
+            if float.is_nan() || float < f64::from(i32::MIN) || float > f64::from(i32::MAX) {
+                return Err(format!(
+                    "{} It is outside of the INT range.",
+                    cannot_convert(value, DataType::INT)
+                ));
+            }
+            Ok(Box::new(float as i32))
+        }
+        DataType::CHAR => Ok(Box::new(digit(value, DataType::INT)? as i32)),
+        DataType::BOOL => Ok(Box::new(i32::from(
+            *DataType::downcast_box_any::<bool>(value).unwrap(),
+        ))),
+        _ => parse(value, DataType::INT),
+    };
+}
+
+fn to_float(arguments: &[Box<dyn Any>]) -> Result<Box<dyn Any>, String> {
+    let value = &arguments[0];
+    return match DataType::box_any_to_data_type(value).unwrap() {
+        DataType::INT | DataType::FLOAT => Ok(Box::new(number(value))),
+        DataType::CHAR => Ok(Box::new(f64::from(digit(value, DataType::FLOAT)?))),
+        DataType::BOOL => Ok(Box::new(f64::from(u8::from(
+            *DataType::downcast_box_any::<bool>(value).unwrap(),
+        )))),
+        _ => parse(value, DataType::FLOAT),
+    };
+}
+
+fn to_char(arguments: &[Box<dyn Any>]) -> Result<Box<dyn Any>, String> {
+    let value = &arguments[0];
+    return match DataType::box_any_to_data_type(value).unwrap() {
+        DataType::CHAR => Ok(Box::new(text(value).chars().next().unwrap())),
+        DataType::INT => match u32::try_from(int(value))
+            .ok()
+            .and_then(|digit| char::from_digit(digit, 10))
+        {
+            Some(character) => Ok(Box::new(character)),
+            None => Err(format!(
+                "{} Expected a digit from 0 to 9.",
+                cannot_convert(value, DataType::CHAR)
+            )),
+        },
+        DataType::STR => {
+            let characters: Vec<char> = text(value).chars().collect();
+            match characters[..] {
+                [character] => Ok(Box::new(character)),
+                _ => Err(format!(
+                    "{} Expected exactly one character.",
+                    cannot_convert(value, DataType::CHAR)
+                )),
+            }
+        }
+        _ => Err(cannot_convert(value, DataType::CHAR)),
+    };
+}
+
+fn to_bool(arguments: &[Box<dyn Any>]) -> Result<Box<dyn Any>, String> {
+    let value = &arguments[0];
+    return match DataType::box_any_to_data_type(value).unwrap() {
+        DataType::BOOL => Ok(Box::new(
+            *DataType::downcast_box_any::<bool>(value).unwrap(),
+        )),
+        DataType::INT | DataType::FLOAT => Ok(Box::new(number(value) != 0.0)),
+        DataType::STR => parse(value, DataType::BOOL),
+        _ => Err(cannot_convert(value, DataType::BOOL)),
+    };
+}
+
+fn chr(arguments: &[Box<dyn Any>]) -> Result<Box<dyn Any>, String> {
+    let code = int(&arguments[0]);
+    return match u32::try_from(code).ok().and_then(char::from_u32) {
+        Some(character) => Ok(Box::new(character)),
+        None => Err(format!("{} is not a valid UNICODE code point.", code)),
+    };
+}
+
+enum Piece {
+    Text(String),
+    Value(Placeholder),
+}
+
+// A `{:[<>^][0][width][.precision]}` placeholder of FORMAT.
+struct Placeholder {
+    align: Option<char>,
+    zero: bool,
+    width: usize,
+    precision: Option<usize>,
+}
+
+// The largest width or precision of a FORMAT placeholder.
+const FORMAT_LIMIT: usize = 1000;
+
+fn digits(characters: &[char], index: &mut usize) -> String {
+    let start = *index;
+    while *index < characters.len() && characters[*index].is_ascii_digit() {
+        *index += 1;
+    }
+    return characters[start..*index].iter().collect();
+}
+
+fn placeholder(specification: &str) -> Result<Placeholder, String> {
+    let invalid = || format!("Invalid format specification '{{:{}}}'.", specification);
+    let size = |digits: String| -> Result<Option<usize>, String> {
+        if digits.is_empty() {
+            return Ok(None);
+        }
+        return match digits.parse::<usize>() {
+            Ok(size) if size <= FORMAT_LIMIT => Ok(Some(size)),
+            _ => Err(format!(
+                "Invalid format specification '{{:{}}}', the width and precision can be at most {}.",
+                specification, FORMAT_LIMIT
+            )),
+        };
+    };
+    let characters: Vec<char> = specification.chars().collect();
+    let mut index = 0;
+    let align = match characters.first() {
+        Some(align @ ('<' | '>' | '^')) => {
+            index += 1;
+            Some(*align)
+        }
+        _ => None,
+    };
+    let zero = characters.get(index) == Some(&'0');
+    if zero {
+        index += 1;
+    }
+    let width = size(digits(&characters, &mut index))?.unwrap_or(0);
+    let mut precision = None;
+    if characters.get(index) == Some(&'.') {
+        index += 1;
+        precision = Some(size(digits(&characters, &mut index))?.ok_or_else(invalid)?);
+    }
+    if index != characters.len() {
+        return Err(invalid());
+    }
+    return Ok(Placeholder {
+        align,
+        zero,
+        width,
+        precision,
+    });
+}
+
+fn pieces(format: &str) -> Result<Vec<Piece>, String> {
+    let mut pieces = Vec::new();
+    let mut text = String::new();
+    let mut characters = format.chars().peekable();
+    while let Some(character) = characters.next() {
+        match character {
+            '{' if characters.peek() == Some(&'{') => {
+                characters.next();
+                text.push('{');
+            }
+            '}' if characters.peek() == Some(&'}') => {
+                characters.next();
+                text.push('}');
+            }
+            '{' => {
+                let mut inside = String::new();
+                loop {
+                    match characters.next() {
+                        Some('}') => break,
+                        Some(character) => inside.push(character),
+                        None => return Err("Unclosed '{' in the format.".to_owned()),
+                    }
+                }
+                let specification = match inside.strip_prefix(':') {
+                    Some(specification) => specification,
+                    None if inside.is_empty() => "",
+                    None => {
+                        return Err(format!(
+                        "Invalid placeholder '{{{}}}', expected '{{}}' or '{{:<specification>}}'.",
+                        inside
+                    ))
+                    }
+                };
+                pieces.push(Piece::Text(std::mem::take(&mut text)));
+                pieces.push(Piece::Value(placeholder(specification)?));
+            }
+            '}' => return Err("Unmatched '}' in the format, write '}}' for a brace.".to_owned()),
+            character => text.push(character),
+        }
+    }
+    pieces.push(Piece::Text(text));
+    return Ok(pieces);
+}
+
+// Validates a FORMAT string known while parsing, so that mistakes are
+// reported before the program runs.
+pub fn check_format(format: &str, values: usize) -> Result<(), String> {
+    let placeholders = pieces(format)?
+        .iter()
+        .filter(|piece| matches!(piece, Piece::Value(_)))
+        .count();
+    if placeholders != values {
+        return Err(format!(
+            "The format has {} placeholder(s) but got {} value(s).",
+            placeholders, values
+        ));
+    }
+    return Ok(());
+}
+
+fn format_value(value: &Box<dyn Any>, placeholder: &Placeholder) -> Result<String, String> {
+    let is_number = matches!(
+        DataType::box_any_to_data_type(value),
+        Some(DataType::INT | DataType::FLOAT)
+    );
+    let text = match placeholder.precision {
+        Some(precision) if is_number => format!("{:.*}", precision, number(value)),
+        Some(precision) => DataType::stringify_primitives(value)?
+            .chars()
+            .take(precision)
+            .collect(),
+        None => DataType::stringify_primitives(value)?,
+    };
+    let padding = placeholder.width.saturating_sub(text.chars().count());
+    if placeholder.zero {
+        if !is_number {
+            return Err("Zero padding is only for INT and FLOAT values.".to_owned());
+        }
+        let (sign, digits) = match text.strip_prefix('-') {
+            Some(digits) => ("-", digits),
+            None => ("", text.as_str()),
+        };
+        return Ok(format!("{}{}{}", sign, "0".repeat(padding), digits));
+    }
+    let align = placeholder
+        .align
+        .unwrap_or(if is_number { '>' } else { '<' });
+    let (left, right) = match align {
+        '<' => (0, padding),
+        '>' => (padding, 0),
+        _ => (padding / 2, padding - padding / 2),
+    };
+    return Ok(format!("{}{}{}", " ".repeat(left), text, " ".repeat(right)));
+}
+
+fn format_text(arguments: &[Box<dyn Any>]) -> Result<Box<dyn Any>, String> {
+    let format = text(&arguments[0]);
+    check_format(&format, arguments.len() - 1)?;
+    let mut values = arguments[1..].iter();
+    let mut result = String::new();
+    for piece in pieces(&format)? {
+        match piece {
+            Piece::Text(text) => result.push_str(&text),
+            Piece::Value(placeholder) => {
+                result.push_str(&format_value(values.next().unwrap(), &placeholder)?)
+            }
+        }
+    }
+    return Ok(Box::new(result));
+}
@@ -1,6 +1,6 @@
-use std::any::Any;
+use std::{any::Any, fmt::Display};
 
-use crate::{token::Token, token_type::TokenType};
+use crate::{lexeme, token::Token, token_type::TokenType};
 
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub enum DataType {
@@ -9,6 +9,71 @@ pub enum DataType {
     CHAR,
     BOOL,
     STR,
+    ARRAY(Box<DataType>, usize),
+    RECORD(String),
+}
+
+pub struct Record {
+    pub name: String,
+    pub fields: Vec<(String, Box<dyn Any>)>,
+}
+
+impl Record {
+    pub fn get(&self, field: &str) -> Option<&Box<dyn Any>> {
+        return self
+            .fields
+            .iter()
+            .find(|(name, _)| name == field)
+            .map(|(_, value)| value);
+    }
+
+    pub fn get_mut(&mut self, field: &str) -> Option<&mut Box<dyn Any>> {
+        return self
+            .fields
+            .iter_mut()
+            .find(|(name, _)| name == field)
+            .map(|(_, value)| value);
+    }
+}
+
+// The inclusive bounds of a `VAR x AS INT RANGE low TO high` variable.
+#[derive(Clone)]
+pub struct Range {
+    pub low: f64,
+    pub high: f64,
+}
+
+impl Range {
+    pub fn check(&self, name: &str, value: &Box<dyn Any>) -> Result<(), String> {
+        let number = if let Some(value) = DataType::downcast_box_any::<i32>(value) {
+            f64::from(*value)
+        } else if let Some(value) = DataType::downcast_box_any::<f64>(value) {
+            *value
+        } else {
+            return Ok(());
+        };
+        if number < self.low || number > self.high {
+            return Err(format!(
+                "Value {} is outside of the RANGE {} TO {} of '{}'.",
+                DataType::stringify_primitives(value)?,
+                self.low,
+                self.high,
+                name
+            ));
+        }
+        return Ok(());
+    }
+}
+
+impl Display for DataType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DataType::STR => write!(f, "STRING"),
+            DataType::ARRAY(data_type, length) => write!(f, "{}[{}]", data_type, length),
+            DataType::RECORD(name) => write!(f, "{}", name),
+            _ => write!(f, "{:?}", self),
+        }
+    }
 }
 
 impl DataType {
@@ -23,6 +88,19 @@ impl DataType {
             output.to_string().to_uppercase()
         } else if let Some(output) = object.downcast_ref::<String>() {
             output.clone()
+        } else if let Some(record) = object.downcast_ref::<Record>() {
+            let fields = record
+                .fields
+                .iter()
+                .map(|(name, value)| {
+                    Ok(format!(
+                        "{}: {}",
+                        name,
+                        DataType::stringify_primitives(value)?
+                    ))
+                })
+                .collect::<Result<Vec<String>, String>>()?;
+            format!("{}({})", record.name, fields.join(", "))
         } else {
             return Err("Invalid data type.".to_owned());
         };
@@ -44,6 +122,13 @@ impl DataType {
             Some(DataType::BOOL)
         } else if (*object).downcast_ref::<String>().is_some() {
             Some(DataType::STR)
+        } else if let Some(record) = (*object).downcast_ref::<Record>() {
+            Some(DataType::RECORD(record.name.clone()))
+        } else if let Some(array) = (*object).downcast_ref::<Vec<Box<dyn Any>>>() {
+            Some(DataType::ARRAY(
+                Box::new(DataType::box_any_to_data_type(array.first()?)?),
+                array.len(),
+            ))
         } else {
             None
         };
@@ -54,7 +139,7 @@ impl DataType {
             TokenType::LitBool => {
                 Some(Box::new(str.trim().to_lowercase().parse::<bool>().unwrap()))
             }
-            TokenType::LitChar => Some(Box::new(str.trim().parse::<char>().unwrap())),
+            TokenType::LitChar => Some(Box::new(str.parse::<char>().unwrap())),
             TokenType::LitFloat => Some(Box::new(str.trim().parse::<f64>().unwrap())),
             TokenType::LitInt => Some(Box::new(str.trim().parse::<i32>().unwrap())),
             TokenType::LitStr => Some(Box::new(str.to_owned())),
@@ -62,6 +147,87 @@ impl DataType {
         };
     }
 
+    pub fn parse_literal(value: &str, data_type: &DataType) -> Result<Box<dyn Any>, String> {
+        let value = value.trim();
+        let vec: Vec<char> = value.chars().collect();
+        return match data_type {
+            DataType::INT | DataType::FLOAT => {
+                let (sign, digits) = match vec.first() {
+                    Some('-') => ("-", &vec[1..]),
+                    Some('+') => ("", &vec[1..]),
+                    _ => ("", &vec[..]),
+                };
+                let is_number = digits
+                    .first()
+                    .is_some_and(|&first| first == '.' || first.is_ascii_digit());
+                let number = match is_number {
+                    false => None,
+                    true => match lexeme::number_dfa(digits, 0) {
+                        Ok((lexeme, index, token_type)) if index == digits.len() - 1 => {
+                            Some((format!("{sign}{lexeme}"), token_type))
+                        }
+                        _ => None,
+                    },
+                };
+                match (number, data_type) {
+                    (Some((number, TokenType::LitInt)), DataType::INT) => {
+                        match number.parse::<i32>() {
+                            Ok(number) => Ok(Box::new(number)),
+                            Err(_) => Err("Value is out of the 32-bit INT range.".to_owned()),
+                        }
+                    }
+                    (Some((number, _)), DataType::FLOAT) => {
+                        Ok(Box::new(number.parse::<f64>().unwrap()))
+                    }
+                    (_, DataType::INT) => {
+                        Err("Expected a whole number such as 42 or -7.".to_owned())
+                    }
+                    _ => Err("Expected a number such as 3.14 or -2.".to_owned()),
+                }
+            }
+            DataType::BOOL => {
+                let mut quoted = vec.clone();
+                if !quoted
+                    .first()
+                    .is_some_and(|&first| lexeme::is_double_quote(first))
+                {
+                    quoted.insert(0, '"');
+                    quoted.push('"');
+                }
+                match lexeme::bool_dfa(&quoted, 0) {
+                    Ok((lexeme, index)) if !lexeme.is_empty() && index == quoted.len() - 1 => {
+                        Ok(Box::new(lexeme == "TRUE"))
+                    }
+                    _ => Err("Expected TRUE or FALSE.".to_owned()),
+                }
+            }
+            DataType::CHAR => {
+                let is_quoted = vec.len() >= 2
+                    && lexeme::is_single_quote(vec[0])
+                    && lexeme::is_single_quote(vec[vec.len() - 1]);
+                if !is_quoted {
+                    return match vec.len() {
+                        1 => Ok(Box::new(vec[0])),
+                        _ => Err("Expected a single character.".to_owned()),
+                    };
+                }
+                if vec.len() == 2 {
+                    return Ok(Box::new('\0'));
+                }
+                match lexeme::special_characters(&vec, 1) {
+                    Ok((lexeme, index)) if index == vec.len() - 2 => {
+                        Ok(Box::new(lexeme.chars().next().unwrap()))
+                    }
+                    _ if vec.len() == 3 => Ok(Box::new(vec[1])),
+                    _ => Err("Expected a single character or escape code.".to_owned()),
+                }
+            }
+            DataType::STR => Ok(Box::new(value.to_owned())),
+            DataType::ARRAY(..) => Err("Invalid array data type.".to_owned()),
+            DataType::RECORD(..) => Err("Invalid record data type.".to_owned()),
+        };
+    }
+
     pub fn clone_ref_any(object: &Box<dyn Any>) -> Option<Box<dyn Any>> {
         let data_type = DataType::box_any_to_data_type(object)?;
         let mut object = object;
@@ -74,6 +240,27 @@ impl DataType {
             DataType::CHAR => Box::new(*(*object).downcast_ref::<char>().unwrap()),
             DataType::BOOL => Box::new(*(*object).downcast_ref::<bool>().unwrap()),
             DataType::STR => Box::new((*object).downcast_ref::<String>().unwrap().clone()),
+            DataType::ARRAY(..) => Box::new(
+                (*object)
+                    .downcast_ref::<Vec<Box<dyn Any>>>()
+                    .unwrap()
+                    .iter()
+                    .map(DataType::clone_ref_any)
+                    .collect::<Option<Vec<Box<dyn Any>>>>()?,
+            ),
+            DataType::RECORD(name) => {
+                let record = (*object).downcast_ref::<Record>().unwrap();
+                Box::new(Record {
+                    name,
+                    fields: record
+                        .fields
+                        .iter()
+                        .map(|(field, value)| {
+                            Some((field.clone(), DataType::clone_ref_any(value)?))
+                        })
+                        .collect::<Option<Vec<(String, Box<dyn Any>)>>>()?,
+                })
+            }
         };
         return Some(value);
     }
@@ -115,10 +302,7 @@ impl DataType {
         //     return Err("Operand must be booleans.".to_owned());
         // }
         if left_dt != right_dt {
-            return Err(format!(
-                "Mismatched types of {:?} and {:?}",
-                left_dt, right_dt
-            ));
+            return Err(format!("Mismatched types of {} and {}", left_dt, right_dt));
         }
         return match left_dt {
             DataType::INT => {
@@ -146,6 +330,8 @@ impl DataType {
                 let right_value = right.downcast_ref::<String>().unwrap();
                 Ok(left_value == right_value)
             }
+            DataType::ARRAY(..) => Err("Arrays cannot be compared.".to_owned()),
+            DataType::RECORD(..) => Err("Records cannot be compared.".to_owned()),
         };
     }
 
@@ -155,6 +341,7 @@ impl DataType {
             TokenType::RkwChar => Some(Box::new('\0')),
             TokenType::RkwFloat => Some(Box::new(0.0)),
             TokenType::RkwInt => Some(Box::new(0)),
+            TokenType::RkwString => Some(Box::new(String::new())),
             _ => None,
         };
     }
@@ -165,10 +352,32 @@ impl DataType {
             TokenType::RkwChar => Some(DataType::CHAR),
             TokenType::RkwFloat => Some(DataType::FLOAT),
             TokenType::RkwInt => Some(DataType::INT),
+            TokenType::RkwString => Some(DataType::STR),
             _ => None,
         }
     }
 
+    pub fn is_assignable(target: &DataType, value: &DataType) -> bool {
+        return target == value || (*target == DataType::FLOAT && *value == DataType::INT);
+    }
+
+    pub fn coerce(value: Box<dyn Any>, data_type: &DataType) -> Result<Box<dyn Any>, String> {
+        let value_data_type = match DataType::box_any_to_data_type(&value) {
+            Some(value_data_type) => value_data_type,
+            None => return Err(format!("Expected {} type.", data_type)),
+        };
+        if *data_type == DataType::FLOAT && value_data_type == DataType::INT {
+            return Ok(Box::new(f64::from(*value.downcast_ref::<i32>().unwrap())));
+        }
+        if value_data_type != *data_type {
+            return Err(format!(
+                "Expected {} type but got {}.",
+                data_type, value_data_type
+            ));
+        }
+        return Ok(value);
+    }
+
     pub fn downcast_box_any<T: 'static>(box_any: &Box<dyn Any>) -> Option<&T> {
         let mut object = box_any;
         while let Some(value) = (*object).downcast_ref::<Box<dyn Any>>() {
@@ -254,4 +463,60 @@ mod test {
             DataType::stringify_primitives(&(Box::new(1.1 as f32) as Box<dyn Any>))
         );
     }
+
+    #[test]
+    fn parse_literal_int() {
+        let value = DataType::parse_literal(" +5 ", &DataType::INT).unwrap();
+        assert_eq!(Some(&5), value.downcast_ref::<i32>());
+        let value = DataType::parse_literal("-60", &DataType::INT).unwrap();
+        assert_eq!(Some(&-60), value.downcast_ref::<i32>());
+        assert!(DataType::parse_literal("1.5", &DataType::INT).is_err());
+        assert!(DataType::parse_literal("5a", &DataType::INT).is_err());
+        assert!(DataType::parse_literal("abc", &DataType::INT).is_err());
+        assert!(DataType::parse_literal("-", &DataType::INT).is_err());
+        assert!(DataType::parse_literal("", &DataType::INT).is_err());
+        assert!(DataType::parse_literal("99999999999", &DataType::INT).is_err());
+    }
+
+    #[test]
+    fn parse_literal_float() {
+        let value = DataType::parse_literal("-.5", &DataType::FLOAT).unwrap();
+        assert_eq!(Some(&-0.5), value.downcast_ref::<f64>());
+        let value = DataType::parse_literal("2", &DataType::FLOAT).unwrap();
+        assert_eq!(Some(&2.0), value.downcast_ref::<f64>());
+        assert!(DataType::parse_literal("1.2.3", &DataType::FLOAT).is_err());
+        assert!(DataType::parse_literal("1e5", &DataType::FLOAT).is_err());
+    }
+
+    #[test]
+    fn parse_literal_bool() {
+        let value = DataType::parse_literal("TRUE", &DataType::BOOL).unwrap();
+        assert_eq!(Some(&true), value.downcast_ref::<bool>());
+        let value = DataType::parse_literal("\"FALSE\"", &DataType::BOOL).unwrap();
+        assert_eq!(Some(&false), value.downcast_ref::<bool>());
+        assert!(DataType::parse_literal("true", &DataType::BOOL).is_err());
+        assert!(DataType::parse_literal("TRUEX", &DataType::BOOL).is_err());
+    }
+
+    #[test]
+    fn parse_literal_char() {
+        let value = DataType::parse_literal("a", &DataType::CHAR).unwrap();
+        assert_eq!(Some(&'a'), value.downcast_ref::<char>());
+        let value = DataType::parse_literal("'#'", &DataType::CHAR).unwrap();
+        assert_eq!(Some(&'\n'), value.downcast_ref::<char>());
+        let value = DataType::parse_literal("'[#]'", &DataType::CHAR).unwrap();
+        assert_eq!(Some(&'#'), value.downcast_ref::<char>());
+        assert!(DataType::parse_literal("ab", &DataType::CHAR).is_err());
+    }
+
+    #[test]
+    fn parse_literal_str() {
+        let value = DataType::parse_literal(" Juan dela Cruz\n", &DataType::STR).unwrap();
+        assert_eq!(
+            Some(&String::from("Juan dela Cruz")),
+            value.downcast_ref::<String>()
+        );
+        let value = DataType::parse_literal("", &DataType::STR).unwrap();
+        assert_eq!(Some(&String::new()), value.downcast_ref::<String>());
+    }
 }
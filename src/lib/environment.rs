@@ -1,31 +1,129 @@
-use std::{
-    any::Any,
-    collections::{hash_map::Entry, HashMap},
+use std::{any::Any, collections::HashMap, mem, rc::Rc};
+
+use crate::{
+    data_type::{DataType, Range},
+    interpreter::Options,
+    statement::function::Callable,
 };
 
-use crate::data_type::DataType;
+#[derive(Default)]
+pub struct Scope {
+    pub variables: HashMap<String, Box<dyn Any>>,
+    pub ranges: HashMap<String, Range>,
+    pub parent: Option<Box<Scope>>,
+}
+
+impl Scope {
+    pub fn new(variables: HashMap<String, Box<dyn Any>>) -> Scope {
+        Scope {
+            variables,
+            ranges: HashMap::new(),
+            parent: None,
+        }
+    }
+
+    // The RANGE of the variable in the scope that declares it, a shadowing
+    // variable without a RANGE hides the one outside.
+    fn range(&self, name: &str) -> Option<&Range> {
+        if self.variables.contains_key(name) {
+            return self.ranges.get(name);
+        }
+        return self.parent.as_ref()?.range(name);
+    }
+
+    fn get(&self, name: &str) -> Option<&Box<dyn Any>> {
+        return match self.variables.get(name) {
+            Some(value) => Some(value),
+            None => self.parent.as_ref()?.get(name),
+        };
+    }
+
+    fn get_mut(&mut self, name: &str) -> Option<&mut Box<dyn Any>> {
+        if self.variables.contains_key(name) {
+            return self.variables.get_mut(name);
+        }
+        return self.parent.as_mut()?.get_mut(name);
+    }
+}
 
 pub struct Environment {
-    pub variables: HashMap<String, Box<dyn Any>>,
+    pub scope: Scope,
+    pub functions: HashMap<String, Rc<Callable>>,
+    pub call_depth: usize,
+    pub options: Options,
+    // Set by THROW and INPUT so that CATCH can tell where an error came from.
+    pub error_kind: Option<&'static str>,
 }
 
 impl Environment {
+    pub fn new(options: Options) -> Environment {
+        Environment {
+            scope: Scope::default(),
+            functions: HashMap::new(),
+            call_depth: 0,
+            options,
+            error_kind: None,
+        }
+    }
+
+    pub fn begin_scope(&mut self) {
+        let parent = mem::take(&mut self.scope);
+        self.scope.parent = Some(Box::new(parent));
+    }
+
+    pub fn end_scope(&mut self) {
+        if let Some(parent) = self.scope.parent.take() {
+            self.scope = *parent;
+        }
+    }
+
     pub fn define(&mut self, name: String, value: Box<dyn Any>) -> Result<(), &'static str> {
-        self.variables.insert(name, value);
+        self.scope.variables.insert(name, value);
         return Ok(());
     }
 
-    pub fn assign(&mut self, name: String, value: Box<dyn Any>) -> Result<(), &'static str> {
-        if let Entry::Occupied(mut variables) = self.variables.entry(name) {
-            variables.insert(value);
+    pub fn define_range(&mut self, name: String, range: Range) {
+        self.scope.ranges.insert(name, range);
+    }
+
+    pub fn check_range(&self, name: &str, value: &Box<dyn Any>) -> Result<(), String> {
+        return match self.scope.range(name) {
+            Some(range) => range.check(name, value),
+            None => Ok(()),
+        };
+    }
+
+    pub fn assign(&mut self, name: String, value: Box<dyn Any>) -> Result<(), String> {
+        self.check_range(&name, &value)?;
+        if let Some(variable) = self.scope.get_mut(&name) {
+            *variable = value;
         } else {
-            return Err("Undefined variable '{name}'.");
+            return Err(format!("Undefined variable '{name}'."));
         }
         return Ok(());
     }
 
     pub fn get(&self, name: &str) -> Result<&Box<dyn Any>, &'static str> {
-        if let Some(value) = self.variables.get(name) {
+        if let Some(value) = self.scope.get(name) {
+            return Ok(value);
+        } else {
+            return Err("Undefined variable '{name}'.");
+        }
+    }
+
+    pub fn define_function(&mut self, name: String, function: Rc<Callable>) {
+        self.functions.insert(name, function);
+    }
+
+    pub fn get_function(&self, name: &str) -> Result<Rc<Callable>, String> {
+        return match self.functions.get(name) {
+            Some(function) => Ok(Rc::clone(function)),
+            None => Err(format!("Undefined function '{name}'.")),
+        };
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Result<&mut Box<dyn Any>, &'static str> {
+        if let Some(value) = self.scope.get_mut(name) {
             return Ok(value);
         } else {
             return Err("Undefined variable '{name}'.");
@@ -33,7 +131,7 @@ impl Environment {
     }
 
     pub fn data_type(&self, name: &str) -> Result<DataType, &'static str> {
-        let object = self.variables.get(name);
+        let object = self.scope.get(name);
         if object.is_none() {
             return Err("Undefined variable '{name}'.");
         }
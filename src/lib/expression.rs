@@ -3,15 +3,23 @@ use std::any::Any;
 use crate::environment::Environment;
 
 use self::{
-    assign::Assign, binary::Binary, grouping::Grouping, literal::Literal, logical::Logical,
-    unary::Unary, variable::Variable,
+    assign::Assign, binary::Binary, builtin_call::BuiltinCall, call::Call,
+    compound_assign::CompoundAssign, conditional::Conditional, get::Get, grouping::Grouping,
+    index::Index, literal::Literal, logical::Logical, set::Set, unary::Unary, variable::Variable,
 };
 
 pub mod assign;
 pub mod binary;
+pub mod builtin_call;
+pub mod call;
+pub mod compound_assign;
+pub mod conditional;
+pub mod get;
 pub mod grouping;
+pub mod index;
 pub mod literal;
 pub mod logical;
+pub mod set;
 pub mod unary;
 pub mod variable;
 
@@ -35,6 +43,20 @@ pub fn display_expression(expression: &Box<dyn Expression>) -> String {
         return expression.to_string();
     } else if let Some(expression) = (*expression).as_any().downcast_ref::<Variable>() {
         return expression.to_string();
+    } else if let Some(expression) = (*expression).as_any().downcast_ref::<Call>() {
+        return expression.to_string();
+    } else if let Some(expression) = (*expression).as_any().downcast_ref::<BuiltinCall>() {
+        return expression.to_string();
+    } else if let Some(expression) = (*expression).as_any().downcast_ref::<Index>() {
+        return expression.to_string();
+    } else if let Some(expression) = (*expression).as_any().downcast_ref::<Set>() {
+        return expression.to_string();
+    } else if let Some(expression) = (*expression).as_any().downcast_ref::<Get>() {
+        return expression.to_string();
+    } else if let Some(expression) = (*expression).as_any().downcast_ref::<Conditional>() {
+        return expression.to_string();
+    } else if let Some(expression) = (*expression).as_any().downcast_ref::<CompoundAssign>() {
+        return expression.to_string();
     }
     return "".to_owned();
 }
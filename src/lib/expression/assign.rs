@@ -1,6 +1,8 @@
 use std::{any::Any, fmt::Display};
 
-use crate::{data_type::DataType, environment::Environment, token::Token};
+use crate::{
+    data_type::DataType, environment::Environment, interpreter::error_string_token, token::Token,
+};
 
 use super::Expression;
 
@@ -12,16 +14,12 @@ pub struct Assign {
 
 impl Expression for Assign {
     fn visit(&self, environment: &mut Environment) -> Result<Box<dyn std::any::Any>, String> {
-        let mut value = self.value.visit(environment)?;
-        let data_type = DataType::box_any_to_data_type(&value);
-        if data_type.is_none() {
-            return Err("Expected expression value as '{token_type}'.".to_owned());
-        }
-        if self.data_type == DataType::FLOAT && data_type.unwrap() == DataType::INT {
-            value = Box::new(f64::from(*value.downcast_ref::<i32>().unwrap()));
-        }
+        let value = DataType::coerce(self.value.visit(environment)?, &self.data_type)
+            .map_err(|error| error_string_token(&self.name, &error))?;
         let return_value = DataType::clone_ref_any(&value);
-        environment.assign(self.name.lexeme.clone(), value)?;
+        environment
+            .assign(self.name.lexeme.clone(), value)
+            .map_err(|error| error_string_token(&self.name, &error))?;
         return Ok(return_value.unwrap());
     }
 
@@ -1,8 +1,11 @@
 use std::{any::Any, fmt::Display};
 
-use crate::{data_type::DataType, environment::Environment, token::Token, token_type::TokenType};
+use crate::{
+    data_type::DataType, environment::Environment, interpreter::error_string_token, token::Token,
+    token_type::TokenType,
+};
 
-use super::Expression;
+use super::{grouping::Grouping, literal::Literal, Expression};
 
 pub struct Binary {
     pub operator: Token,
@@ -10,6 +13,32 @@ pub struct Binary {
     pub right: Box<dyn Expression>,
 }
 
+impl Binary {
+    // An INT raised to an INT is only an INT when the exponent is written as a
+    // non-negative INT, i.e. `2 ^ 10`, so that the type is known before the
+    // program runs whatever value the exponent has.
+    pub fn is_int_exponent(expression: &dyn Expression) -> bool {
+        if let Some(literal) = expression.as_any().downcast_ref::<Literal>() {
+            return DataType::downcast_box_any::<i32>(&literal.value)
+                .is_some_and(|value| *value >= 0);
+        }
+        if let Some(grouping) = expression.as_any().downcast_ref::<Grouping>() {
+            return Binary::is_int_exponent(&*grouping.expression);
+        }
+        return false;
+    }
+
+    // The result of an INT operation, which is None when it does not fit in an
+    // INT or, for `/` and `%`, on a zero divisor.
+    fn checked_int(&self, value: Option<i32>, right: i32) -> Result<Box<dyn Any>, String> {
+        return match value {
+            Some(value) => Ok(Box::new(value)),
+            None if right == 0 => Err(error_string_token(&self.operator, "Division by zero.")),
+            None => Err(error_string_token(&self.operator, "INT overflow.")),
+        };
+    }
+}
+
 impl Expression for Binary {
     fn visit(&self, environment: &mut Environment) -> Result<Box<dyn std::any::Any>, String> {
         type TupleOkResult = (Box<dyn Any>, DataType, Box<dyn Any>, DataType);
@@ -17,7 +46,17 @@ impl Expression for Binary {
             |environment: &mut Environment| -> Result<TupleOkResult, String> {
                 let left_value = self.left.visit(environment)?;
                 let right_value = self.right.visit(environment)?;
-                DataType::is_are_operands_number(&[&left_value, &right_value])?;
+                let is_string_comparison = matches!(
+                    self.operator.token_type,
+                    TokenType::SymGreater
+                        | TokenType::SymGreaterEqual
+                        | TokenType::SymLesser
+                        | TokenType::SymLesserEqual
+                ) && left_value.downcast_ref::<String>().is_some()
+                    && right_value.downcast_ref::<String>().is_some();
+                if !is_string_comparison {
+                    DataType::is_are_operands_number(&[&left_value, &right_value])?;
+                }
                 let left_dt = DataType::box_any_to_data_type(&left_value);
                 let right_dt = DataType::box_any_to_data_type(&right_value);
                 if left_dt.is_none() || right_dt.is_none() {
@@ -45,6 +84,10 @@ impl Expression for Binary {
                     let left_value = left_value.downcast_ref::<i32>().unwrap();
                     let right_value = right_value.downcast_ref::<f64>().unwrap();
                     return Ok(Box::new(f64::from(*left_value) > *right_value));
+                } else if left_dt == DataType::STR && right_dt == DataType::STR {
+                    let left_value = left_value.downcast_ref::<String>().unwrap();
+                    let right_value = right_value.downcast_ref::<String>().unwrap();
+                    return Ok(Box::new(left_value > right_value));
                 } else {
                     return Err("Operand must be a number.".to_owned());
                 }
@@ -68,6 +111,10 @@ impl Expression for Binary {
                     let left_value = left_value.downcast_ref::<i32>().unwrap();
                     let right_value = right_value.downcast_ref::<f64>().unwrap();
                     return Ok(Box::new(f64::from(*left_value) >= *right_value));
+                } else if left_dt == DataType::STR && right_dt == DataType::STR {
+                    let left_value = left_value.downcast_ref::<String>().unwrap();
+                    let right_value = right_value.downcast_ref::<String>().unwrap();
+                    return Ok(Box::new(left_value >= right_value));
                 } else {
                     return Err("Operand must be a number.".to_owned());
                 }
@@ -91,6 +138,10 @@ impl Expression for Binary {
                     let left_value = left_value.downcast_ref::<i32>().unwrap();
                     let right_value = right_value.downcast_ref::<f64>().unwrap();
                     return Ok(Box::new(f64::from(*left_value) < *right_value));
+                } else if left_dt == DataType::STR && right_dt == DataType::STR {
+                    let left_value = left_value.downcast_ref::<String>().unwrap();
+                    let right_value = right_value.downcast_ref::<String>().unwrap();
+                    return Ok(Box::new(left_value < right_value));
                 } else {
                     return Err("Operand must be a number.".to_owned());
                 }
@@ -114,6 +165,10 @@ impl Expression for Binary {
                     let left_value = left_value.downcast_ref::<i32>().unwrap();
                     let right_value = right_value.downcast_ref::<f64>().unwrap();
                     return Ok(Box::new(f64::from(*left_value) <= *right_value));
+                } else if left_dt == DataType::STR && right_dt == DataType::STR {
+                    let left_value = left_value.downcast_ref::<String>().unwrap();
+                    let right_value = right_value.downcast_ref::<String>().unwrap();
+                    return Ok(Box::new(left_value <= right_value));
                 } else {
                     return Err("Operand must be a number.".to_owned());
                 }
@@ -140,7 +195,7 @@ impl Expression for Binary {
                 } else if left_dt == DataType::INT && right_dt == DataType::INT {
                     let left_value = left_value.downcast_ref::<i32>().unwrap();
                     let right_value = right_value.downcast_ref::<i32>().unwrap();
-                    return Ok(Box::new(left_value - right_value));
+                    return self.checked_int(left_value.checked_sub(*right_value), *right_value);
                 } else if left_dt == DataType::FLOAT && right_dt == DataType::INT {
                     let left_value = left_value.downcast_ref::<f64>().unwrap();
                     let right_value = right_value.downcast_ref::<i32>().unwrap();
@@ -163,7 +218,7 @@ impl Expression for Binary {
                 } else if left_dt == DataType::INT && right_dt == DataType::INT {
                     let left_value = left_value.downcast_ref::<i32>().unwrap();
                     let right_value = right_value.downcast_ref::<i32>().unwrap();
-                    return Ok(Box::new(left_value + right_value));
+                    return self.checked_int(left_value.checked_add(*right_value), *right_value);
                 } else if left_dt == DataType::FLOAT && right_dt == DataType::INT {
                     let left_value = left_value.downcast_ref::<f64>().unwrap();
                     let right_value = right_value.downcast_ref::<i32>().unwrap();
@@ -186,7 +241,7 @@ impl Expression for Binary {
                 } else if left_dt == DataType::INT && right_dt == DataType::INT {
                     let left_value = left_value.downcast_ref::<i32>().unwrap();
                     let right_value = right_value.downcast_ref::<i32>().unwrap();
-                    return Ok(Box::new(left_value / right_value));
+                    return self.checked_int(left_value.checked_div(*right_value), *right_value);
                 } else if left_dt == DataType::FLOAT && right_dt == DataType::INT {
                     let left_value = left_value.downcast_ref::<f64>().unwrap();
                     let right_value = right_value.downcast_ref::<i32>().unwrap();
@@ -209,7 +264,7 @@ impl Expression for Binary {
                 } else if left_dt == DataType::INT && right_dt == DataType::INT {
                     let left_value = left_value.downcast_ref::<i32>().unwrap();
                     let right_value = right_value.downcast_ref::<i32>().unwrap();
-                    return Ok(Box::new(left_value * right_value));
+                    return self.checked_int(left_value.checked_mul(*right_value), *right_value);
                 } else if left_dt == DataType::FLOAT && right_dt == DataType::INT {
                     let left_value = left_value.downcast_ref::<f64>().unwrap();
                     let right_value = right_value.downcast_ref::<i32>().unwrap();
@@ -228,11 +283,83 @@ impl Expression for Binary {
                 if left_dt == DataType::INT && right_dt == DataType::INT {
                     let left_value = left_value.downcast_ref::<i32>().unwrap();
                     let right_value = right_value.downcast_ref::<i32>().unwrap();
-                    return Ok(Box::new(left_value % right_value));
+                    return self.checked_int(left_value.checked_rem(*right_value), *right_value);
                 } else {
                     return Err("Operand must be a number.".to_owned());
                 }
             }
+            TokenType::SymPower => {
+                let (left_value, left_dt, right_value, right_dt) =
+                    get_values_data_type(environment)?;
+                if left_dt == DataType::INT
+                    && right_dt == DataType::INT
+                    && Binary::is_int_exponent(&*self.right)
+                {
+                    let left_value = *left_value.downcast_ref::<i32>().unwrap();
+                    let right_value = *right_value.downcast_ref::<i32>().unwrap();
+                    return match left_value.checked_pow(right_value as u32) {
+                        Some(value) => Ok(Box::new(value)),
+                        None => Err(error_string_token(
+                            &self.operator,
+                            &format!(
+                                "{} {} {} is too large for an INT.",
+                                left_value, self.operator.lexeme, right_value
+                            ),
+                        )),
+                    };
+                }
+                let left_value = match DataType::downcast_box_any::<i32>(&left_value) {
+                    Some(value) => f64::from(*value),
+                    None => *left_value.downcast_ref::<f64>().unwrap(),
+                };
+                let right_value = match DataType::downcast_box_any::<i32>(&right_value) {
+                    Some(value) => f64::from(*value),
+                    None => *right_value.downcast_ref::<f64>().unwrap(),
+                };
+                return Ok(Box::new(left_value.powf(right_value)));
+            }
+            TokenType::RkwBand
+            | TokenType::RkwBor
+            | TokenType::RkwBxor
+            | TokenType::RkwShl
+            | TokenType::RkwShr => {
+                let left_value = self.left.visit(environment)?;
+                let right_value = self.right.visit(environment)?;
+                let (left_value, right_value) = match (
+                    DataType::downcast_box_any::<i32>(&left_value),
+                    DataType::downcast_box_any::<i32>(&right_value),
+                ) {
+                    (Some(left_value), Some(right_value)) => (*left_value, *right_value),
+                    _ => {
+                        return Err(error_string_token(
+                            &self.operator,
+                            &format!(
+                                "Expected INT operands for {} but got {} and {}.",
+                                self.operator.lexeme,
+                                DataType::box_any_to_data_type(&left_value).unwrap(),
+                                DataType::box_any_to_data_type(&right_value).unwrap()
+                            ),
+                        ))
+                    }
+                };
+                if matches!(
+                    self.operator.token_type,
+                    TokenType::RkwShl | TokenType::RkwShr
+                ) && !(0..32).contains(&right_value)
+                {
+                    return Err(error_string_token(
+                        &self.operator,
+                        &format!("Shift amount {} is outside of 0 to 31.", right_value),
+                    ));
+                }
+                return Ok(Box::new(match self.operator.token_type {
+                    TokenType::RkwBand => left_value & right_value,
+                    TokenType::RkwBor => left_value | right_value,
+                    TokenType::RkwBxor => left_value ^ right_value,
+                    TokenType::RkwShl => left_value << right_value,
+                    _ => left_value >> right_value,
+                }));
+            }
             TokenType::SymAmpersand => {
                 return Ok(Box::new(
                     DataType::stringify_primitives(&self.left.visit(environment)?)?
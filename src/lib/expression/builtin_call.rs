@@ -0,0 +1,63 @@
+use std::{any::Any, fmt::Display};
+
+use crate::{
+    builtin::Builtin, data_type::DataType, environment::Environment,
+    interpreter::error_string_token, token::Token,
+};
+
+use super::{display_expression, Expression};
+
+pub struct BuiltinCall {
+    pub name: Token,
+    pub builtin: &'static Builtin,
+    pub arguments: Vec<Box<dyn Expression>>,
+    pub data_type: Option<DataType>,
+}
+
+impl Expression for BuiltinCall {
+    fn visit(&self, environment: &mut Environment) -> Result<Box<dyn Any>, String> {
+        let mut arguments = Vec::new();
+        for (index, argument) in self.arguments.iter().enumerate() {
+            let value = argument.visit(environment)?;
+            let parameter = self.builtin.parameter(index);
+            match DataType::box_any_to_data_type(&value) {
+                Some(data_type) if parameter.accepts(&data_type) => arguments.push(value),
+                data_type => {
+                    return Err(error_string_token(
+                        &self.name,
+                        &format!(
+                            "Expected {} argument for '{}' but got {}.",
+                            parameter,
+                            self.builtin.name,
+                            data_type.map_or("an invalid value".to_owned(), |data_type| {
+                                data_type.to_string()
+                            })
+                        ),
+                    ))
+                }
+            }
+        }
+        return (self.builtin.call)(&arguments).map_err(|error| {
+            error_string_token(
+                &self.name,
+                &format!("{} failed. {}", self.builtin.name, error),
+            )
+        });
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl Display for BuiltinCall {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let arguments: Vec<String> = self.arguments.iter().map(display_expression).collect();
+        write!(
+            f,
+            "BuiltinCall({:?}, [{}])",
+            self.name,
+            arguments.join(", ")
+        )
+    }
+}
@@ -0,0 +1,46 @@
+use std::{any::Any, fmt::Display};
+
+use crate::{environment::Environment, interpreter::error_string_token, token::Token};
+
+use super::{display_expression, Expression};
+
+pub struct Call {
+    pub name: Token,
+    pub arguments: Vec<Box<dyn Expression>>,
+}
+
+impl Call {
+    pub fn invoke(&self, environment: &mut Environment) -> Result<Option<Box<dyn Any>>, String> {
+        let mut arguments = Vec::new();
+        for argument in &self.arguments {
+            arguments.push(argument.visit(environment)?);
+        }
+        let function = environment
+            .get_function(&self.name.lexeme)
+            .map_err(|error| error_string_token(&self.name, &error))?;
+        return function.call(environment, &self.name, arguments);
+    }
+}
+
+impl Expression for Call {
+    fn visit(&self, environment: &mut Environment) -> Result<Box<dyn Any>, String> {
+        return match self.invoke(environment)? {
+            Some(value) => Ok(value),
+            None => Err(error_string_token(
+                &self.name,
+                &format!("SUB '{}' does not return a value.", self.name.lexeme),
+            )),
+        };
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl Display for Call {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let arguments: Vec<String> = self.arguments.iter().map(display_expression).collect();
+        write!(f, "Call({:?}, [{}])", self.name, arguments.join(", "))
+    }
+}
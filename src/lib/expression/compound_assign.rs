@@ -0,0 +1,53 @@
+use std::{any::Any, fmt::Display};
+
+use crate::{
+    data_type::DataType, environment::Environment, interpreter::error_string_token, token::Token,
+};
+
+use super::{binary::Binary, display_expression, literal::Literal, set::Place, Expression};
+
+// `x += 1`, `a[i] &= "s"` or `x++`, which work like `x = x + 1` except that
+// the indexes of the target are only evaluated once.
+pub struct CompoundAssign {
+    pub token: Token,
+    pub operator: Token,
+    pub target: Box<dyn Expression>,
+    pub value: Box<dyn Expression>,
+    pub data_type: DataType,
+}
+
+impl Expression for CompoundAssign {
+    fn visit(&self, environment: &mut Environment) -> Result<Box<dyn Any>, String> {
+        let place = Place::resolve(&*self.target, environment)?;
+        let current = place.read(environment)?;
+        let value = self.value.visit(environment)?;
+        let value = Binary {
+            operator: self.operator.clone(),
+            left: Box::new(Literal { value: current }),
+            right: Box::new(Literal { value }),
+        }
+        .visit(environment)?;
+        let value = DataType::coerce(value, &self.data_type)
+            .map_err(|error| error_string_token(&self.token, &error))?;
+        let return_value = DataType::clone_ref_any(&value);
+        place.write(environment, value)?;
+        return Ok(return_value.unwrap());
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl Display for CompoundAssign {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "CompoundAssign({}, {:?}, {:?}, {})",
+            display_expression(&self.target),
+            self.operator,
+            self.data_type,
+            display_expression(&self.value)
+        )
+    }
+}
@@ -0,0 +1,52 @@
+use std::{any::Any, fmt::Display};
+
+use crate::{
+    data_type::DataType, environment::Environment, interpreter::error_string_token, token::Token,
+};
+
+use super::{display_expression, Expression};
+
+pub struct Conditional {
+    pub token: Token,
+    pub condition: Box<dyn Expression>,
+    pub then_branch: Box<dyn Expression>,
+    pub else_branch: Box<dyn Expression>,
+    pub data_type: Option<DataType>,
+}
+
+impl Expression for Conditional {
+    fn visit(&self, environment: &mut Environment) -> Result<Box<dyn Any>, String> {
+        let condition = self.condition.visit(environment)?;
+        let value = match DataType::downcast_box_any::<bool>(&condition) {
+            Some(true) => self.then_branch.visit(environment)?,
+            Some(false) => self.else_branch.visit(environment)?,
+            None => {
+                return Err(error_string_token(
+                    &self.token,
+                    "Expected a BOOL condition for IIF.",
+                ))
+            }
+        };
+        return match &self.data_type {
+            Some(data_type) => DataType::coerce(value, data_type)
+                .map_err(|error| error_string_token(&self.token, &error)),
+            None => Ok(value),
+        };
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl Display for Conditional {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Conditional({}, {}, {})",
+            display_expression(&self.condition),
+            display_expression(&self.then_branch),
+            display_expression(&self.else_branch)
+        )
+    }
+}
@@ -0,0 +1,61 @@
+use std::{any::Any, fmt::Display};
+
+use crate::{
+    data_type::{DataType, Record},
+    environment::Environment,
+    interpreter::error_string_token,
+    token::Token,
+};
+
+use super::{display_expression, Expression};
+
+pub struct Get {
+    pub object: Box<dyn Expression>,
+    pub name: Token,
+}
+
+impl Get {
+    pub fn expect_record(&self, value: &Box<dyn Any>) -> Result<(), String> {
+        if DataType::downcast_box_any::<Record>(value).is_none() {
+            return Err(error_string_token(
+                &self.name,
+                &format!("Expected a record to get '{}' from.", self.name.lexeme),
+            ));
+        }
+        return Ok(());
+    }
+
+    pub fn undefined_field(&self) -> String {
+        return error_string_token(
+            &self.name,
+            &format!("Undefined field '{}'.", self.name.lexeme),
+        );
+    }
+}
+
+impl Expression for Get {
+    fn visit(&self, environment: &mut Environment) -> Result<Box<dyn Any>, String> {
+        let record = self.object.visit(environment)?;
+        self.expect_record(&record)?;
+        let record = DataType::downcast_box_any::<Record>(&record).unwrap();
+        return match record.get(&self.name.lexeme) {
+            Some(value) => Ok(DataType::clone_ref_any(value).unwrap()),
+            None => Err(self.undefined_field()),
+        };
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl Display for Get {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Get({}, {:?})",
+            display_expression(&self.object),
+            self.name
+        )
+    }
+}
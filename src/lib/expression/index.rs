@@ -0,0 +1,71 @@
+use std::{any::Any, fmt::Display};
+
+use crate::{
+    data_type::DataType, environment::Environment, interpreter::error_string_token, token::Token,
+};
+
+use super::{display_expression, Expression};
+
+pub struct Index {
+    pub name: Token,
+    pub index: Box<dyn Expression>,
+}
+
+impl Index {
+    pub fn position(&self, environment: &mut Environment) -> Result<usize, String> {
+        let index = self.index.visit(environment)?;
+        let index = match DataType::downcast_box_any::<i32>(&index) {
+            Some(index) => *index,
+            None => return Err(error_string_token(&self.name, "Expected INT array index.")),
+        };
+        let length = match DataType::downcast_box_any::<Vec<Box<dyn Any>>>(
+            environment.get(&self.name.lexeme)?,
+        ) {
+            Some(array) => array.len(),
+            None => {
+                return Err(error_string_token(
+                    &self.name,
+                    &format!("'{}' is not an array.", self.name.lexeme),
+                ))
+            }
+        };
+        if index < 0 || index as usize >= length {
+            return Err(error_string_token(
+                &self.name,
+                &format!(
+                    "Index {} is out of bounds for '{}' of length {}.",
+                    index, self.name.lexeme, length
+                ),
+            ));
+        }
+        return Ok(index as usize);
+    }
+}
+
+impl Expression for Index {
+    fn visit(&self, environment: &mut Environment) -> Result<Box<dyn Any>, String> {
+        let position = self.position(environment)?;
+        let array =
+            DataType::downcast_box_any::<Vec<Box<dyn Any>>>(environment.get(&self.name.lexeme)?)
+                .unwrap();
+        return match DataType::clone_ref_any(&array[position]) {
+            Some(value) => Ok(value),
+            None => Err("Cannot clone array element.".to_owned()),
+        };
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl Display for Index {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Index({:?}, {})",
+            self.name,
+            display_expression(&self.index)
+        )
+    }
+}
@@ -0,0 +1,132 @@
+use std::{any::Any, fmt::Display};
+
+use crate::{
+    data_type::{DataType, Record},
+    environment::Environment,
+    interpreter::error_string_token,
+    token::Token,
+};
+
+use super::{display_expression, get::Get, index::Index, variable::Variable, Expression};
+
+pub struct Set {
+    pub token: Token,
+    pub target: Box<dyn Expression>,
+    pub value: Box<dyn Expression>,
+    pub data_type: DataType,
+}
+
+// An assignment target with its array indexes already evaluated, so that it
+// can be read and written without running the index expressions again.
+pub enum Place<'a> {
+    Variable(&'a Token),
+    Element(&'a Index, usize),
+    Field(Box<Place<'a>>, &'a Get),
+}
+
+impl<'a> Place<'a> {
+    pub fn resolve(
+        target: &'a dyn Expression,
+        environment: &mut Environment,
+    ) -> Result<Place<'a>, String> {
+        if let Some(target) = target.as_any().downcast_ref::<Variable>() {
+            return Ok(Place::Variable(&target.name));
+        } else if let Some(target) = target.as_any().downcast_ref::<Index>() {
+            return Ok(Place::Element(target, target.position(environment)?));
+        } else if let Some(target) = target.as_any().downcast_ref::<Get>() {
+            let object = Place::resolve(&*target.object, environment)?;
+            return Ok(Place::Field(Box::new(object), target));
+        }
+        return Err("Invalid assignment target.".to_owned());
+    }
+
+    pub fn read(&self, environment: &mut Environment) -> Result<Box<dyn Any>, String> {
+        let value = match self {
+            Place::Variable(name) => environment
+                .get(&name.lexeme)
+                .map_err(|error| error_string_token(name, error))
+                .map(DataType::clone_ref_any)?,
+            Place::Element(target, position) => {
+                let array = environment.get(&target.name.lexeme)?;
+                DataType::clone_ref_any(
+                    &DataType::downcast_box_any::<Vec<Box<dyn Any>>>(array).unwrap()[*position],
+                )
+            }
+            Place::Field(object, target) => {
+                let record = object.read(environment)?;
+                target.expect_record(&record)?;
+                match DataType::downcast_box_any::<Record>(&record)
+                    .unwrap()
+                    .get(&target.name.lexeme)
+                {
+                    Some(value) => DataType::clone_ref_any(value),
+                    None => return Err(target.undefined_field()),
+                }
+            }
+        };
+        return Ok(value.unwrap());
+    }
+
+    // A field is set on a copy of the record which is then written back, so
+    // that any place holding it (a variable, an array element or another
+    // field) is supported.
+    pub fn write(&self, environment: &mut Environment, value: Box<dyn Any>) -> Result<(), String> {
+        match self {
+            Place::Variable(name) => environment
+                .assign(name.lexeme.clone(), value)
+                .map_err(|error| error_string_token(name, &error))?,
+            Place::Element(target, position) => {
+                let array = environment.get_mut(&target.name.lexeme)?;
+                (**array).downcast_mut::<Vec<Box<dyn Any>>>().unwrap()[*position] = value;
+            }
+            Place::Field(object, target) => {
+                let mut record = object.read(environment)?;
+                target.expect_record(&record)?;
+                match (*record)
+                    .downcast_mut::<Record>()
+                    .unwrap()
+                    .get_mut(&target.name.lexeme)
+                {
+                    Some(field) => *field = value,
+                    None => return Err(target.undefined_field()),
+                }
+                object.write(environment, record)?;
+            }
+        }
+        return Ok(());
+    }
+}
+
+pub fn assign_target(
+    target: &dyn Expression,
+    environment: &mut Environment,
+    value: Box<dyn Any>,
+) -> Result<(), String> {
+    return Place::resolve(target, environment)?.write(environment, value);
+}
+
+impl Expression for Set {
+    fn visit(&self, environment: &mut Environment) -> Result<Box<dyn Any>, String> {
+        let value = DataType::coerce(self.value.visit(environment)?, &self.data_type)
+            .map_err(|error| error_string_token(&self.token, &error))?;
+        let return_value = DataType::clone_ref_any(&value);
+        assign_target(&*self.target, environment, value)?;
+        return Ok(return_value.unwrap());
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl Display for Set {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Set({}, {:?}, {})",
+            display_expression(&self.target),
+            self.data_type,
+            display_expression(&self.value)
+        )
+    }
+}
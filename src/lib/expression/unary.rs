@@ -1,6 +1,9 @@
 use std::{any::Any, fmt::Display};
 
-use crate::{data_type::DataType, environment::Environment, token::Token, token_type::TokenType};
+use crate::{
+    data_type::DataType, environment::Environment, interpreter::error_string_token, token::Token,
+    token_type::TokenType,
+};
 
 use super::Expression;
 
@@ -43,6 +46,19 @@ impl Expression for Unary {
                     _ => Err("Operand must be a number.".to_owned()),
                 };
             }
+            TokenType::RkwBnot => {
+                let expression = self.right.visit(environment)?;
+                return match DataType::downcast_box_any::<i32>(&expression) {
+                    Some(value) => Ok(Box::new(!value)),
+                    None => Err(error_string_token(
+                        &self.operator,
+                        &format!(
+                            "Expected INT type for BNOT but got {}.",
+                            DataType::box_any_to_data_type(&expression).unwrap()
+                        ),
+                    )),
+                };
+            }
             _ => return Err("Invalid unary operator.".to_owned()),
         };
     }
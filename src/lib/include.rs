@@ -0,0 +1,167 @@
+use std::{
+    fs,
+    path::{Component, Path, PathBuf},
+    rc::Rc,
+};
+
+use crate::{lexer, source_code::SourceCode, token::Token, token_type::TokenType};
+
+struct Includes {
+    // Files whose INCLUDEs are being resolved, from the main program inwards.
+    including: Vec<(PathBuf, String)>,
+    // Files already spliced in, a file included twice is only added once.
+    included: Vec<PathBuf>,
+}
+
+pub fn include_files(
+    source_code: &mut SourceCode,
+    tokens: Vec<Token>,
+    file_path: Option<&Path>,
+) -> Result<Vec<Token>, String> {
+    let mut includes = Includes {
+        including: Vec::new(),
+        included: Vec::new(),
+    };
+    let directory = match file_path {
+        Some(file_path) => {
+            if let Ok(path) = fs::canonicalize(file_path) {
+                includes
+                    .including
+                    .push((path, file_path.display().to_string()));
+            }
+            file_path.parent().unwrap_or(Path::new("")).to_path_buf()
+        }
+        None => PathBuf::new(),
+    };
+    return expand(source_code, tokens, &directory, &mut includes);
+}
+
+// Drops `.` and `..` parts so that diagnostics show a readable path.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => (),
+            Component::ParentDir
+                if matches!(
+                    normalized.components().next_back(),
+                    Some(Component::Normal(_))
+                ) =>
+            {
+                normalized.pop();
+            }
+            _ => normalized.push(component),
+        }
+    }
+    return normalized;
+}
+
+fn expand(
+    source_code: &mut SourceCode,
+    tokens: Vec<Token>,
+    directory: &Path,
+    includes: &mut Includes,
+) -> Result<Vec<Token>, String> {
+    let mut result = Vec::new();
+    let mut index = 0;
+    while tokens[index].token_type == TokenType::RkwInclude {
+        let path_token = &tokens[index + 1];
+        if path_token.token_type != TokenType::LitStr {
+            return Err(source_code.error_string_token(
+                path_token,
+                "Expected the file path as a string after 'INCLUDE'.",
+            ));
+        }
+        let end = &tokens[index + 2];
+        if !matches!(end.token_type, TokenType::Eol | TokenType::Eof) {
+            return Err(
+                source_code.error_string_token(end, "Expected new line after the file path.")
+            );
+        }
+        index += if end.token_type == TokenType::Eol {
+            3
+        } else {
+            2
+        };
+
+        let path = normalize(&directory.join(&path_token.lexeme));
+        let file_name = path.display().to_string();
+        let canonical = match fs::canonicalize(&path) {
+            Ok(canonical) => canonical,
+            Err(_) => {
+                return Err(source_code
+                    .error_string_token(path_token, &format!("File not found: {file_name}")))
+            }
+        };
+        if let Some(position) = includes
+            .including
+            .iter()
+            .position(|(including, _)| *including == canonical)
+        {
+            let mut cycle: Vec<&str> = includes.including[position..]
+                .iter()
+                .map(|(_, name)| name.as_str())
+                .collect();
+            cycle.push(&file_name);
+            return Err(source_code.error_string_token(
+                path_token,
+                &format!("Include cycle: {}.", cycle.join(" -> ")),
+            ));
+        }
+        if includes.included.contains(&canonical) {
+            continue;
+        }
+        includes.included.push(canonical.clone());
+
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(_) => {
+                return Err(source_code.error_string_token(
+                    path_token,
+                    &format!("Error opening the file: {file_name}"),
+                ))
+            }
+        };
+        if contents.is_empty() {
+            continue;
+        }
+        let mut included_source_code = SourceCode::new(contents, Some(file_name.clone()));
+        let file: Rc<str> = Rc::from(file_name.as_str());
+        let mut included_tokens = lexer::lexical_analysis(&included_source_code)?;
+        for token in &mut included_tokens {
+            token.file = Some(Rc::clone(&file));
+        }
+
+        includes.including.push((canonical, file_name));
+        let mut included_tokens = expand(
+            &mut included_source_code,
+            included_tokens,
+            path.parent().unwrap_or(Path::new("")),
+            includes,
+        )?;
+        includes.including.pop();
+
+        let eof = included_tokens.pop().unwrap();
+        if included_tokens
+            .last()
+            .is_some_and(|token| token.token_type != TokenType::Eol)
+        {
+            let mut eol = eof;
+            eol.token_type = TokenType::Eol;
+            eol.lexeme = String::from("EOL");
+            included_tokens.push(eol);
+        }
+        result.append(&mut included_tokens);
+        source_code.includes.push(included_source_code);
+    }
+
+    if let Some(token) = tokens[index..]
+        .iter()
+        .find(|token| token.token_type == TokenType::RkwInclude)
+    {
+        return Err(source_code
+            .error_string_token(token, "INCLUDE is only allowed at the top of the program."));
+    }
+    result.extend(tokens.into_iter().skip(index));
+    return Ok(result);
+}
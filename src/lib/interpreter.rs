@@ -1,13 +1,113 @@
-use std::collections::HashMap;
+use std::cell::Cell;
 
-use crate::{environment::Environment, statement::Statement};
+use crate::{
+    environment::Environment,
+    statement::{block::Block, test_stmt::Test, Statement},
+    token::Token,
+};
 
-pub fn interpreter(statements: Vec<Box<dyn Statement>>) -> Result<(), String> {
-    let mut environment = Environment {
-        variables: HashMap::new(),
-    };
+// Stack kept free below the deepest FUNCTION/SUB call for evaluating its body.
+const STACK_MARGIN: usize = 1024 * 1024;
+
+// The lowest stack address a FUNCTION/SUB call may start from, 0 when the
+// program runs on a thread that `guard_stack` was not called on.
+thread_local!(static STACK_LIMIT: Cell<usize> = const { Cell::new(0) });
+
+fn stack_address() -> usize {
+    let marker = 0u8;
+    return std::hint::black_box(&marker) as *const u8 as usize;
+}
+
+// Called first on the thread that runs the program, with the size of its stack.
+pub fn guard_stack(stack_size: usize) {
+    let usable = stack_size.saturating_sub(STACK_MARGIN);
+    STACK_LIMIT.with(|limit| limit.set(stack_address().saturating_sub(usable)));
+}
+
+pub fn is_stack_exhausted() -> bool {
+    return stack_address() < STACK_LIMIT.with(|limit| limit.get());
+}
+
+#[derive(Clone)]
+pub struct Options {
+    pub interactive: bool,
+    pub max_call_depth: usize,
+    pub run_tests: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            interactive: false,
+            max_call_depth: 1000,
+            run_tests: false,
+        }
+    }
+}
+
+pub fn interpreter(statements: Vec<Box<dyn Statement>>, options: &Options) -> Result<(), String> {
+    let mut environment = Environment::new(options.clone());
     for statement in statements {
         statement.visit(&mut environment)?;
     }
     return Ok(());
 }
+
+// Runs every TEST section on its own environment. The declarations of the
+// program are run again for each test, the main START and STOP block is not.
+pub fn run_tests(statements: Vec<Box<dyn Statement>>, options: &Options) -> Result<(), String> {
+    let (tests, declarations): (Vec<_>, Vec<_>) = statements
+        .into_iter()
+        .filter(|statement| statement.as_any().downcast_ref::<Block>().is_none())
+        .partition(|statement| statement.as_any().downcast_ref::<Test>().is_some());
+    if tests.is_empty() {
+        println!("No TEST found.");
+        return Ok(());
+    }
+
+    let mut failed = 0;
+    for test in &tests {
+        let test = test.as_any().downcast_ref::<Test>().unwrap();
+        let mut environment = Environment::new(options.clone());
+        let result = declarations
+            .iter()
+            .chain([&test.body])
+            .try_for_each(|statement| statement.visit(&mut environment).map(|_| ()));
+        match result {
+            Ok(_) => println!("PASS: {}", test.name),
+            Err(error) => {
+                failed += 1;
+                println!("FAIL: {}\n{}", test.name, error);
+            }
+        }
+    }
+    println!("{} passed, {} failed.", tests.len() - failed, failed);
+
+    if failed > 0 {
+        return Err(format!("{} of {} tests failed.", failed, tests.len()));
+    }
+    return Ok(());
+}
+
+pub fn error_string_token(token: &Token, message: &str) -> String {
+    format!("{message}\n{}: {token}", token.location())
+}
+
+// The message of an error without the location line that `error_string_token`
+// adds at its end, the message itself can have more than one line.
+pub fn error_message(error: &str) -> &str {
+    if let Some((message, location)) = error.rsplit_once('\n') {
+        let location = location.split(": ").next().unwrap_or_default();
+        let is_location = location
+            .rsplit_once(":column-")
+            .is_some_and(|(line, column)| {
+                line.rsplit_once("line-")
+                    .is_some_and(|(_, line)| line.parse::<usize>().is_ok())
+                    && column.parse::<usize>().is_ok()
+            });
+        if is_location {
+            return message;
+        }
+    }
+    return error;
+}
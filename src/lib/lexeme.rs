@@ -6,6 +6,8 @@ pub fn static_lexeme_to_token_type(lexeme: &str) -> Result<TokenType, String> {
     match lexeme {
         "(" => Ok(TokenType::SymLeftParenthesis),
         ")" => Ok(TokenType::SymRightParenthesis),
+        "[" => Ok(TokenType::SymLeftBracket),
+        "]" => Ok(TokenType::SymRightBracket),
         "," => Ok(TokenType::SymComma),
         "=" => Ok(TokenType::SymAssignment),
         ":" => Ok(TokenType::SymColon),
@@ -15,12 +17,23 @@ pub fn static_lexeme_to_token_type(lexeme: &str) -> Result<TokenType, String> {
         "*" => Ok(TokenType::SymStar),
         "/" => Ok(TokenType::SymForwardSlash),
         "%" => Ok(TokenType::SymPercent),
+        "." => Ok(TokenType::SymDot),
+        "^" => Ok(TokenType::SymPower),
+        "**" => Ok(TokenType::SymPower),
         ">" => Ok(TokenType::SymGreater),
         "<" => Ok(TokenType::SymLesser),
         ">=" => Ok(TokenType::SymGreaterEqual),
         "<=" => Ok(TokenType::SymLesserEqual),
         "==" => Ok(TokenType::SymEqual),
         "<>" => Ok(TokenType::SymNotEqual),
+        "+=" => Ok(TokenType::SymPlusAssignment),
+        "-=" => Ok(TokenType::SymMinusAssignment),
+        "*=" => Ok(TokenType::SymStarAssignment),
+        "/=" => Ok(TokenType::SymForwardSlashAssignment),
+        "%=" => Ok(TokenType::SymPercentAssignment),
+        "&=" => Ok(TokenType::SymAmpersandAssignment),
+        "++" => Ok(TokenType::SymIncrement),
+        "--" => Ok(TokenType::SymDecrement),
         "AND" => Ok(TokenType::RkwAnd),
         "OR" => Ok(TokenType::RkwOr),
         "NOT" => Ok(TokenType::RkwNot),
@@ -32,11 +45,40 @@ pub fn static_lexeme_to_token_type(lexeme: &str) -> Result<TokenType, String> {
         "BOOL" => Ok(TokenType::RkwBool),
         "FLOAT" => Ok(TokenType::RkwFloat),
         "CHAR" => Ok(TokenType::RkwChar),
+        "STRING" => Ok(TokenType::RkwString),
         "START" => Ok(TokenType::RkwStart),
         "STOP" => Ok(TokenType::RkwStop),
         "IF" => Ok(TokenType::RkwIf),
         "ELSE" => Ok(TokenType::RkwElse),
         "WHILE" => Ok(TokenType::RkwWhile),
+        "FOR" => Ok(TokenType::RkwFor),
+        "TO" => Ok(TokenType::RkwTo),
+        "STEP" => Ok(TokenType::RkwStep),
+        "BREAK" => Ok(TokenType::RkwBreak),
+        "CONTINUE" => Ok(TokenType::RkwContinue),
+        "SWITCH" => Ok(TokenType::RkwSwitch),
+        "CASE" => Ok(TokenType::RkwCase),
+        "DEFAULT" => Ok(TokenType::RkwDefault),
+        "FUNCTION" => Ok(TokenType::RkwFunction),
+        "SUB" => Ok(TokenType::RkwSub),
+        "RETURN" => Ok(TokenType::RkwReturn),
+        "CONST" => Ok(TokenType::RkwConst),
+        "INCLUDE" => Ok(TokenType::RkwInclude),
+        "TYPE" => Ok(TokenType::RkwType),
+        "END" => Ok(TokenType::RkwEnd),
+        "BAND" => Ok(TokenType::RkwBand),
+        "BOR" => Ok(TokenType::RkwBor),
+        "BXOR" => Ok(TokenType::RkwBxor),
+        "BNOT" => Ok(TokenType::RkwBnot),
+        "SHL" => Ok(TokenType::RkwShl),
+        "SHR" => Ok(TokenType::RkwShr),
+        "IIF" => Ok(TokenType::RkwIif),
+        "RANGE" => Ok(TokenType::RkwRange),
+        "TRY" => Ok(TokenType::RkwTry),
+        "CATCH" => Ok(TokenType::RkwCatch),
+        "THROW" => Ok(TokenType::RkwThrow),
+        "ASSERT" => Ok(TokenType::RkwAssert),
+        "TEST" => Ok(TokenType::RkwTest),
         _ => Err(format!("Invalid lexeme {}.", lexeme.escape_debug())),
     }
 }
@@ -52,6 +94,79 @@ pub fn possibly_equal_assignment(
     }
 }
 
+// `++` and `--` are only read as one token at the end of a statement, so that
+// `x--1` is still `x - -1`.
+fn ends_statement(source_code_vec: &[char], index: usize) -> bool {
+    source_code_vec[index + 1..]
+        .iter()
+        .find(|character| !matches!(character, ' ' | '\t' | '\r'))
+        .is_none_or(|character| *character == '\n')
+}
+
+pub fn possibly_plus_increment_assignment(
+    source_code_vec: &[char],
+    index: usize,
+) -> (TokenType, String, usize) {
+    let is_not_eof = index + 1 < source_code_vec.len();
+    if is_not_eof && source_code_vec[index + 1] == '=' {
+        (TokenType::SymPlusAssignment, "+=".to_string(), index + 1)
+    } else if is_not_eof
+        && source_code_vec[index + 1] == '+'
+        && ends_statement(source_code_vec, index + 1)
+    {
+        (TokenType::SymIncrement, "++".to_string(), index + 1)
+    } else {
+        (TokenType::SymPlus, "+".to_string(), index)
+    }
+}
+
+pub fn possibly_minus_decrement_assignment(
+    source_code_vec: &[char],
+    index: usize,
+) -> (TokenType, String, usize) {
+    let is_not_eof = index + 1 < source_code_vec.len();
+    if is_not_eof && source_code_vec[index + 1] == '=' {
+        (TokenType::SymMinusAssignment, "-=".to_string(), index + 1)
+    } else if is_not_eof
+        && source_code_vec[index + 1] == '-'
+        && ends_statement(source_code_vec, index + 1)
+    {
+        (TokenType::SymDecrement, "--".to_string(), index + 1)
+    } else {
+        (TokenType::SymMinus, "-".to_string(), index)
+    }
+}
+
+pub fn possibly_operator_assignment(
+    source_code_vec: &[char],
+    index: usize,
+) -> (TokenType, String, usize) {
+    if source_code_vec[index] == '*'
+        && index + 1 < source_code_vec.len()
+        && source_code_vec[index + 1] == '*'
+    {
+        return (TokenType::SymPower, "**".to_string(), index + 1);
+    }
+    let (token_type, assignment_type) = match source_code_vec[index] {
+        '*' => (TokenType::SymStar, TokenType::SymStarAssignment),
+        '/' => (
+            TokenType::SymForwardSlash,
+            TokenType::SymForwardSlashAssignment,
+        ),
+        '%' => (TokenType::SymPercent, TokenType::SymPercentAssignment),
+        _ => (TokenType::SymAmpersand, TokenType::SymAmpersandAssignment),
+    };
+    if index + 1 < source_code_vec.len() && source_code_vec[index + 1] == '=' {
+        (
+            assignment_type,
+            format!("{}=", source_code_vec[index]),
+            index + 1,
+        )
+    } else {
+        (token_type, source_code_vec[index].to_string(), index)
+    }
+}
+
 pub fn possibly_lesser_lesser_equal_notequal(
     source_code_vec: &[char],
     index: usize,
@@ -187,6 +302,13 @@ pub fn special_characters(
     }
 }
 
+// A '{' in a string literal starts an interpolated expression, except for the
+// `{}`, `{:...}` and `{{` placeholders of FORMAT.
+pub fn is_interpolation(source_code_vec: &[char], index: usize) -> bool {
+    source_code_vec[index] == '{'
+        && !matches!(source_code_vec.get(index + 1), Some('{' | '}' | ':'))
+}
+
 pub fn bool_dfa(
     source_code_vec: &[char],
     index: usize,
@@ -12,14 +12,34 @@ pub fn lexical_analysis(
     COLUMN.with(|data| data.set(0));
     FIRST_IN_LINE.with(|data| data.set(true));
     let mut tokens: Vec<token::Token> = Vec::new();
+    scan(cfpl_source_code, &mut tokens, 0, cfpl_source_code.vec.len())?;
+    LINE.with(|line| {
+        tokens.push(token::Token::new(
+            token_type::TokenType::Eof,
+            String::from("EOF"),
+            line.get(),
+            0,
+        ))
+    });
+    Ok(tokens)
+}
+
+// Turns the characters from start up to end into tokens, starting at the
+// current LINE and COLUMN.
+fn scan(
+    cfpl_source_code: &source_code::SourceCode,
+    tokens: &mut Vec<token::Token>,
+    start: usize,
+    end: usize,
+) -> Result<(), String> {
     let source_code = &cfpl_source_code.vec;
-    let mut i: usize = 0;
+    let mut i: usize = start;
     let _debug_length = source_code.len();
     let mut _debug_current_character = source_code[i];
     let mut _debug_current_column = COLUMN.with(|column| column.get());
     let mut _debug_current_line = LINE.with(|line| line.get());
     let mut _debug_first_in_line = FIRST_IN_LINE.with(|first_in_line| first_in_line.get());
-    while i < source_code.len() {
+    while i < end {
         _debug_current_character = source_code[i];
         _debug_current_column = COLUMN.with(|column| column.get());
         _debug_current_line = LINE.with(|line| line.get());
@@ -43,39 +63,76 @@ pub fn lexical_analysis(
                     (0, Err(0), false)
                 }
                 '(' => {
-                    single_symbol(cfpl_source_code, &mut tokens, i)?;
+                    single_symbol(cfpl_source_code, tokens, i)?;
                     (0, Ok(0), false)
                 }
                 ')' => {
-                    single_symbol(cfpl_source_code, &mut tokens, i)?;
+                    single_symbol(cfpl_source_code, tokens, i)?;
+                    (0, Ok(0), false)
+                }
+                '[' => {
+                    single_symbol(cfpl_source_code, tokens, i)?;
+                    (0, Ok(0), false)
+                }
+                ']' => {
+                    single_symbol(cfpl_source_code, tokens, i)?;
                     (0, Ok(0), false)
                 }
                 ',' => {
-                    single_symbol(cfpl_source_code, &mut tokens, i)?;
+                    single_symbol(cfpl_source_code, tokens, i)?;
                     (0, Ok(0), false)
                 }
                 ':' => {
-                    single_symbol(cfpl_source_code, &mut tokens, i)?;
+                    single_symbol(cfpl_source_code, tokens, i)?;
                     (0, Ok(0), false)
                 }
                 '&' => {
-                    single_symbol(cfpl_source_code, &mut tokens, i)?;
-                    (0, Ok(0), false)
+                    let index = single_double_symbol(
+                        source_code,
+                        tokens,
+                        i,
+                        lexeme::possibly_operator_assignment,
+                    );
+                    (index - i, Ok(index - i), false)
                 }
                 '+' => {
-                    single_symbol(cfpl_source_code, &mut tokens, i)?;
-                    (0, Ok(0), false)
+                    let index = single_double_symbol(
+                        source_code,
+                        tokens,
+                        i,
+                        lexeme::possibly_plus_increment_assignment,
+                    );
+                    (index - i, Ok(index - i), false)
                 }
                 '-' => {
-                    single_symbol(cfpl_source_code, &mut tokens, i)?;
-                    (0, Ok(0), false)
+                    let index = single_double_symbol(
+                        source_code,
+                        tokens,
+                        i,
+                        lexeme::possibly_minus_decrement_assignment,
+                    );
+                    (index - i, Ok(index - i), false)
                 }
                 '/' => {
-                    single_symbol(cfpl_source_code, &mut tokens, i)?;
-                    (0, Ok(0), false)
+                    let index = single_double_symbol(
+                        source_code,
+                        tokens,
+                        i,
+                        lexeme::possibly_operator_assignment,
+                    );
+                    (index - i, Ok(index - i), false)
                 }
                 '%' => {
-                    single_symbol(cfpl_source_code, &mut tokens, i)?;
+                    let index = single_double_symbol(
+                        source_code,
+                        tokens,
+                        i,
+                        lexeme::possibly_operator_assignment,
+                    );
+                    (index - i, Ok(index - i), false)
+                }
+                '^' => {
+                    single_symbol(cfpl_source_code, tokens, i)?;
                     (0, Ok(0), false)
                 }
                 '*' => {
@@ -85,14 +142,19 @@ pub fn lexical_analysis(
                         FIRST_IN_LINE.with(|first_in_line| first_in_line.set(true));
                         (index - i, Err(0), true)
                     } else {
-                        single_symbol(cfpl_source_code, &mut tokens, i)?;
-                        (0, Ok(0), false)
+                        let index = single_double_symbol(
+                            source_code,
+                            tokens,
+                            i,
+                            lexeme::possibly_operator_assignment,
+                        );
+                        (index - i, Ok(index - i), false)
                     }
                 }
                 '=' => {
                     let index = single_double_symbol(
                         source_code,
-                        &mut tokens,
+                        tokens,
                         i,
                         lexeme::possibly_equal_assignment,
                     );
@@ -101,7 +163,7 @@ pub fn lexical_analysis(
                 '<' => {
                     let index = single_double_symbol(
                         source_code,
-                        &mut tokens,
+                        tokens,
                         i,
                         lexeme::possibly_lesser_lesser_equal_notequal,
                     );
@@ -110,7 +172,7 @@ pub fn lexical_analysis(
                 '>' => {
                     let index = single_double_symbol(
                         source_code,
-                        &mut tokens,
+                        tokens,
                         i,
                         lexeme::possibly_greater_greater_equal,
                     );
@@ -118,25 +180,32 @@ pub fn lexical_analysis(
                 }
                 other => {
                     if lexeme::is_single_quote(other) {
-                        let index = character_literal(cfpl_source_code, &mut tokens, i)?;
+                        let index = character_literal(cfpl_source_code, tokens, i)?;
                         (index - i, Ok(index - i), false)
                     } else if lexeme::is_double_quote(other) {
-                        if let Some(index_result) = bool_literal(cfpl_source_code, &mut tokens, i) {
+                        if let Some(index_result) = bool_literal(cfpl_source_code, tokens, i) {
                             (index_result - i, Ok(index_result - i), false)
                         } else {
                             let (index_result, line_result, column_result) =
-                                string_literal(cfpl_source_code, &mut tokens, i)?;
+                                string_literal(cfpl_source_code, tokens, i)?;
                             LINE.with(|line| line.set(line_result));
                             (index_result - i, Err(column_result + 1), false)
                         }
+                    } else if source_code[i] == '.'
+                        && !source_code
+                            .get(i + 1)
+                            .is_some_and(|next| next.is_ascii_digit())
+                    {
+                        single_symbol(cfpl_source_code, tokens, i)?;
+                        (0, Ok(0), false)
                     } else if source_code[i] == '.' || source_code[i].is_ascii_digit() {
-                        let index = number_literal(cfpl_source_code, &mut tokens, i)?;
+                        let index = number_literal(cfpl_source_code, tokens, i)?;
                         (index - i, Ok(index - i), false)
                     } else if source_code[i] == '_'
                         || source_code[i] == '$'
                         || source_code[i].is_ascii_alphabetic()
                     {
-                        let index = words(cfpl_source_code, &mut tokens, i)?;
+                        let index = words(cfpl_source_code, tokens, i)?;
                         (index - i, Ok(index - i), false)
                     } else if source_code[i].is_whitespace() {
                         (0, Ok(0), false)
@@ -163,15 +232,7 @@ pub fn lexical_analysis(
             Err(no_increment_value) => COLUMN.with(|column| column.set(no_increment_value)),
         }
     }
-    LINE.with(|line| {
-        tokens.push(token::Token::new(
-            token_type::TokenType::Eof,
-            String::from("EOF"),
-            line.get(),
-            0,
-        ))
-    });
-    Ok(tokens)
+    Ok(())
 }
 
 fn single_symbol(
@@ -355,6 +416,8 @@ fn string_literal(
     let start_index = index;
     let mut line = token_line;
     let mut column = token_column + 1;
+    let mut interpolated = false;
+    let (mut part_line, mut part_column) = (token_line, token_column);
     index += 1;
     while index < source_code.len() {
         if source_code[index] == '\n' {
@@ -364,6 +427,40 @@ fn string_literal(
         if lexeme::is_double_quote(source_code[index]) {
             break;
         }
+        if source_code[index] == '{' && source_code.get(index + 1) == Some(&'{') {
+            literal_value.push_str("{{");
+            index += 2;
+            column += 2;
+            continue;
+        }
+        if lexeme::is_interpolation(source_code, index) {
+            let end = interpolation_end(cfpl_source_code, index, line, column)?;
+            if !interpolated {
+                interpolated = true;
+                push_symbol(tokens, "(", part_line, part_column);
+                tokens.push(token::Token::new(
+                    token_type::TokenType::LitStr,
+                    std::mem::take(&mut literal_value),
+                    part_line,
+                    part_column,
+                ));
+            } else {
+                push_string_part(tokens, &mut literal_value, part_line, part_column);
+            }
+            push_symbol(tokens, "&", line, column);
+            push_symbol(tokens, "(", line, column);
+            LINE.with(|data| data.set(line));
+            COLUMN.with(|data| data.set(column + 1));
+            FIRST_IN_LINE.with(|data| data.set(false));
+            scan(cfpl_source_code, tokens, index + 1, end)?;
+            column += end - index;
+            index = end;
+            push_symbol(tokens, ")", line, column);
+            (part_line, part_column) = (line, column);
+            index += 1;
+            column += 1;
+            continue;
+        }
         literal_value.push_str(
             match lexeme::special_characters(source_code, index) {
                 Ok((lexeme_result, index_result)) => {
@@ -396,13 +493,81 @@ fn string_literal(
         index += 1;
         column += 1;
     }
+    if interpolated {
+        push_string_part(tokens, &mut literal_value, part_line, part_column);
+        push_symbol(tokens, ")", line, column);
+    } else {
+        tokens.push(token::Token::new(
+            token_type::TokenType::LitStr,
+            literal_value,
+            token_line,
+            token_column,
+        ));
+    }
+    Ok((index, line, column))
+}
+
+// Finds the '}' that closes the interpolation starting at index, it has to be
+// on the same line and inside the string.
+fn interpolation_end(
+    cfpl_source_code: &source_code::SourceCode,
+    index: usize,
+    line: usize,
+    column: usize,
+) -> Result<usize, String> {
+    let source_code = &cfpl_source_code.vec;
+    let mut end = index + 1;
+    while end < source_code.len() && source_code[end] != '}' {
+        if source_code[end] == '\n' || lexeme::is_double_quote(source_code[end]) {
+            break;
+        }
+        end += 1;
+    }
+    let error_message = if end >= source_code.len() || source_code[end] != '}' {
+        "Unclosed '{' in the string, write [{] for a brace."
+    } else if source_code[index + 1..end]
+        .iter()
+        .all(|character| character.is_whitespace())
+    {
+        "Expected an expression inside '{' and '}'."
+    } else {
+        return Ok(end);
+    };
+    Err(cfpl_source_code.error_string_manual(
+        line,
+        column,
+        source_code[index..end.min(source_code.len())]
+            .iter()
+            .collect::<String>(),
+        error_message.to_string(),
+    ))
+}
+
+fn push_symbol(tokens: &mut Vec<token::Token>, lexeme: &str, line: usize, column: usize) {
+    tokens.push(token::Token::new(
+        lexeme::static_lexeme_to_token_type(lexeme).unwrap(),
+        lexeme.to_string(),
+        line,
+        column,
+    ));
+}
+
+fn push_string_part(
+    tokens: &mut Vec<token::Token>,
+    literal_value: &mut String,
+    line: usize,
+    column: usize,
+) {
+    if literal_value.is_empty() {
+        return;
+    }
+    push_symbol(tokens, "&", line, column);
     tokens.push(token::Token::new(
         token_type::TokenType::LitStr,
-        literal_value,
-        token_line,
-        token_column,
+        std::mem::take(literal_value),
+        line,
+        column,
     ));
-    Ok((index, line, column))
 }
 
 fn number_literal(
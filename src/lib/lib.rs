@@ -1,6 +1,8 @@
+pub mod builtin;
 pub mod data_type;
 pub mod environment;
 pub mod expression;
+pub mod include;
 pub mod interpreter;
 pub mod lexeme;
 pub mod lexer;
@@ -9,16 +11,53 @@ pub mod source_code;
 pub mod statement;
 pub mod token;
 pub mod token_type;
-use interpreter::interpreter;
+use interpreter::{interpreter, run_tests, Options};
 use std::fs;
 use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::thread;
 
-pub fn execute(source_code_string: String) -> bool {
-    let cfpl_source_code = source_code::SourceCode {
-        vec: source_code_string.chars().collect(),
-        source_code: source_code_string,
+// Stack reserved for every nested FUNCTION/SUB call, enough for a plain
+// recursion to reach `Options::max_call_depth`. A call through a large
+// expression uses more, `interpreter::guard_stack` stops those with an error.
+const STACK_SIZE_PER_CALL: usize = 64 * 1024;
+const BASE_STACK_SIZE: usize = 8 * 1024 * 1024;
+
+pub fn execute(source_code_string: String, options: &Options) -> bool {
+    return start(source_code_string, None, options);
+}
+
+fn start(source_code_string: String, file_path: Option<PathBuf>, options: &Options) -> bool {
+    let stack_size = options
+        .max_call_depth
+        .checked_mul(STACK_SIZE_PER_CALL)
+        .and_then(|size| size.checked_add(BASE_STACK_SIZE));
+    let options = options.clone();
+    let handle = stack_size.and_then(|stack_size| {
+        thread::Builder::new()
+            .stack_size(stack_size)
+            .spawn(move || {
+                interpreter::guard_stack(stack_size);
+                run(source_code_string, file_path.as_deref(), &options)
+            })
+            .ok()
+    });
+    return match handle {
+        Some(handle) => handle
+            .join()
+            .unwrap_or_else(|panic| std::panic::resume_unwind(panic)),
+        None => {
+            eprint!("Unable to reserve the stack for the maximum call depth.");
+            false
+        }
     };
-    let tokens = match lexer::lexical_analysis(&cfpl_source_code) {
+}
+
+fn run(source_code_string: String, file_path: Option<&Path>, options: &Options) -> bool {
+    let mut cfpl_source_code = source_code::SourceCode::new(source_code_string, None);
+    let tokens = match lexer::lexical_analysis(&cfpl_source_code)
+        .and_then(|tokens| include::include_files(&mut cfpl_source_code, tokens, file_path))
+    {
         Ok(result) => result,
         Err(error) => {
             eprint!("[Lexical-Analysis-Error]: {}", error);
@@ -62,7 +101,12 @@ pub fn execute(source_code_string: String) -> bool {
     //     println!("{}", crate::statement::display_statement(statement));
     // }
 
-    match interpreter(statements) {
+    let result = if options.run_tests {
+        run_tests(statements, options)
+    } else {
+        interpreter(statements, options)
+    };
+    match result {
         Ok(_) => (),
         Err(error) => {
             eprint!("[Interpreter-Error]: {}", error);
@@ -73,18 +117,22 @@ pub fn execute(source_code_string: String) -> bool {
     return true;
 }
 
-pub fn file(file_path: &str) -> bool {
-    return execute(match fs::read_to_string(file_path) {
-        Ok(result) => result,
-        Err(error) => match error.kind() {
-            ErrorKind::NotFound => {
-                eprint!("File not found: {file_path}");
-                return false;
-            }
-            _ => {
-                eprint!("Error opening the file: {file_path}");
-                return false;
-            }
+pub fn file(file_path: &str, options: &Options) -> bool {
+    return start(
+        match fs::read_to_string(file_path) {
+            Ok(result) => result,
+            Err(error) => match error.kind() {
+                ErrorKind::NotFound => {
+                    eprint!("File not found: {file_path}");
+                    return false;
+                }
+                _ => {
+                    eprint!("Error opening the file: {file_path}");
+                    return false;
+                }
+            },
         },
-    });
+        Some(PathBuf::from(file_path)),
+        options,
+    );
 }
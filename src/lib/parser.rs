@@ -1,28 +1,66 @@
-use std::collections::HashMap;
+use std::{any::Any, collections::HashMap, mem, rc::Rc};
 
 use crate::{
-    data_type::DataType,
+    builtin::{self, Builtin},
+    data_type::{DataType, Range, Record},
+    environment::Environment,
     expression::{
-        assign::Assign, binary::Binary, grouping::Grouping, literal::Literal, logical::Logical,
-        unary::Unary, variable::Variable, Expression,
+        assign::Assign, binary::Binary, builtin_call::BuiltinCall, call::Call,
+        compound_assign::CompoundAssign, conditional::Conditional, get::Get, grouping::Grouping,
+        index::Index, literal::Literal, logical::Logical, set::Set, unary::Unary,
+        variable::Variable, Expression,
     },
+    interpreter::Options,
     source_code,
     statement::{
-        self, block::Block, if_stmt::If, input::Input, print::Print, var::Var, var_dec::VarDec,
-        while_stmt::While, Statement,
+        self,
+        assert_stmt::Assert,
+        block::Block,
+        break_stmt::Break,
+        const_dec::ConstDec,
+        continue_stmt::Continue,
+        for_stmt::For,
+        function::{Callable, Function},
+        if_stmt::{Branch, If},
+        input::Input,
+        print::Print,
+        return_stmt::Return,
+        sub_call::SubCall,
+        switch_stmt::Switch,
+        test_stmt::Test,
+        throw_stmt::Throw,
+        try_stmt::Try,
+        type_dec::TypeDec,
+        var::Var,
+        var_dec::VarDec,
+        while_stmt::While,
+        Statement,
     },
     token::{self, Token},
     token_type::TokenType,
 };
 
+type Arguments = (Vec<Token>, Vec<Box<dyn Expression>>);
+
+#[derive(Clone)]
+struct FunctionSignature {
+    parameters: Vec<DataType>,
+    return_type: Option<DataType>,
+}
+
 pub struct Parser<'a> {
     var_declarations: bool,
+    block_declarations: bool,
     declaring: bool,
-    in_control_structure: bool,
     in_scope: bool,
     scope_counter: usize,
+    loop_depth: usize,
     current_index: usize,
-    variable_type: HashMap<String, DataType>,
+    scopes: Vec<HashMap<String, DataType>>,
+    constants: HashMap<String, Box<dyn Any>>,
+    record_types: HashMap<String, Vec<(String, DataType)>>,
+    functions: HashMap<String, FunctionSignature>,
+    current_function: Option<Token>,
     source_code: &'a source_code::SourceCode,
     tokens: &'a Vec<token::Token>,
 }
@@ -34,12 +72,23 @@ impl Parser<'_> {
     ) -> Result<Vec<Box<dyn statement::Statement>>, String> {
         let mut parser = Parser {
             var_declarations: true,
+            block_declarations: false,
             declaring: false,
-            in_control_structure: false,
             in_scope: false,
             scope_counter: 0,
+            loop_depth: 0,
             current_index: 0,
-            variable_type: HashMap::new(),
+            scopes: vec![HashMap::new()],
+            constants: HashMap::new(),
+            record_types: HashMap::from([(
+                "Error".to_owned(),
+                vec![
+                    ("message".to_owned(), DataType::STR),
+                    ("kind".to_owned(), DataType::STR),
+                ],
+            )]),
+            functions: HashMap::new(),
+            current_function: None,
             source_code,
             tokens,
         };
@@ -128,6 +177,9 @@ impl Parser<'_> {
     }
 
     fn expect_logical_expressions(&self, expression: &dyn Expression) -> Result<(), String> {
+        if self.expression_data_type(expression) == Some(DataType::BOOL) {
+            return Ok(());
+        }
         if let Some(expression) = expression.as_any().downcast_ref::<Grouping>() {
             return self.expect_logical_expressions(&*expression.expression);
         }
@@ -159,7 +211,7 @@ impl Parser<'_> {
                 _ => Err(erroneous),
             }
         } else if let Some(expression) = expression.as_any().downcast_ref::<Var>() {
-            match self.variable_type.get(&expression.name.lexeme) {
+            match self.variable_type(&expression.name.lexeme) {
                 Some(data_type) if *data_type == DataType::BOOL => Ok(()),
                 _ => Err(&expression.name),
             }
@@ -175,9 +227,200 @@ impl Parser<'_> {
         };
     }
 
+    fn expression_data_type(&self, expression: &dyn Expression) -> Option<DataType> {
+        if let Some(expression) = expression.as_any().downcast_ref::<Literal>() {
+            return DataType::box_any_to_data_type(&expression.value);
+        } else if let Some(expression) = expression.as_any().downcast_ref::<Variable>() {
+            return self.variable_type(&expression.name.lexeme).cloned();
+        } else if let Some(expression) = expression.as_any().downcast_ref::<Grouping>() {
+            return self.expression_data_type(&*expression.expression);
+        } else if let Some(expression) = expression.as_any().downcast_ref::<Assign>() {
+            return Some(expression.data_type.clone());
+        } else if let Some(expression) = expression.as_any().downcast_ref::<Index>() {
+            return match self.variable_type(&expression.name.lexeme)? {
+                DataType::ARRAY(data_type, _) => Some(*data_type.clone()),
+                _ => None,
+            };
+        } else if let Some(expression) = expression.as_any().downcast_ref::<Set>() {
+            return Some(expression.data_type.clone());
+        } else if let Some(expression) = expression.as_any().downcast_ref::<CompoundAssign>() {
+            return Some(expression.data_type.clone());
+        } else if let Some(expression) = expression.as_any().downcast_ref::<Get>() {
+            return match self.expression_data_type(&*expression.object)? {
+                DataType::RECORD(name) => self.field_type(&name, &expression.name.lexeme),
+                _ => None,
+            };
+        } else if let Some(expression) = expression.as_any().downcast_ref::<BuiltinCall>() {
+            return expression.data_type.clone();
+        } else if let Some(expression) = expression.as_any().downcast_ref::<Conditional>() {
+            return expression.data_type.clone();
+        } else if let Some(expression) = expression.as_any().downcast_ref::<Call>() {
+            return self
+                .functions
+                .get(&expression.name.lexeme)?
+                .return_type
+                .clone();
+        } else if expression.as_any().downcast_ref::<Logical>().is_some() {
+            return Some(DataType::BOOL);
+        } else if let Some(expression) = expression.as_any().downcast_ref::<Unary>() {
+            return match expression.operator.token_type {
+                TokenType::RkwNot => Some(DataType::BOOL),
+                _ => self.expression_data_type(&*expression.right),
+            };
+        } else if let Some(expression) = expression.as_any().downcast_ref::<Binary>() {
+            return match expression.operator.token_type {
+                TokenType::SymLesser
+                | TokenType::SymLesserEqual
+                | TokenType::SymGreater
+                | TokenType::SymGreaterEqual
+                | TokenType::SymEqual
+                | TokenType::SymNotEqual => Some(DataType::BOOL),
+                TokenType::SymAmpersand => Some(DataType::STR),
+                TokenType::SymPercent
+                | TokenType::RkwBand
+                | TokenType::RkwBor
+                | TokenType::RkwBxor
+                | TokenType::RkwShl
+                | TokenType::RkwShr => Some(DataType::INT),
+                _ => {
+                    let left = self.expression_data_type(&*expression.left)?;
+                    let right = self.expression_data_type(&*expression.right)?;
+                    if left == DataType::INT
+                        && right == DataType::INT
+                        && (expression.operator.token_type != TokenType::SymPower
+                            || Binary::is_int_exponent(&*expression.right))
+                    {
+                        Some(DataType::INT)
+                    } else {
+                        Some(DataType::FLOAT)
+                    }
+                }
+            };
+        }
+        return None;
+    }
+
+    fn expect_data_type(
+        &self,
+        token: &Token,
+        data_type: &DataType,
+        expression: &dyn Expression,
+    ) -> Result<(), String> {
+        if let Some(value_data_type) = self.expression_data_type(expression) {
+            if !DataType::is_assignable(data_type, &value_data_type) {
+                return Err(self.source_code.error_string_token(
+                    token,
+                    &format!("Expected {} type but got {}.", data_type, value_data_type),
+                ));
+            }
+        }
+        return Ok(());
+    }
+
+    fn bool_literal_as_str(
+        data_type: &DataType,
+        expression: Box<dyn Expression>,
+    ) -> Box<dyn Expression> {
+        if *data_type == DataType::STR {
+            if let Some(literal) = (*expression).as_any().downcast_ref::<Literal>() {
+                if DataType::downcast_box_any::<bool>(&literal.value).is_some() {
+                    return Box::new(Literal {
+                        value: Box::new(DataType::stringify_primitives(&literal.value).unwrap()),
+                    });
+                }
+            }
+        }
+        return expression;
+    }
+
+    fn variable_type(&self, name: &str) -> Option<&DataType> {
+        return self.scopes.iter().rev().find_map(|scope| scope.get(name));
+    }
+
+    fn field_type(&self, record: &str, field: &str) -> Option<DataType> {
+        return self
+            .record_types
+            .get(record)?
+            .iter()
+            .find(|(name, _)| name == field)
+            .map(|(_, data_type)| data_type.clone());
+    }
+
+    fn data_type(&mut self, message: &str) -> Result<DataType, String> {
+        if self.compare_then_next(&[&TokenType::Identifier]) {
+            let name = self.get_previous().unwrap();
+            if !self.record_types.contains_key(&name.lexeme) {
+                return Err(self
+                    .source_code
+                    .error_string_token(name, &format!("Undefined type '{}'.", name.lexeme)));
+            }
+            return Ok(DataType::RECORD(name.lexeme.clone()));
+        }
+        let token = self.expect_then_next(
+            &[
+                &TokenType::RkwBool,
+                &TokenType::RkwChar,
+                &TokenType::RkwFloat,
+                &TokenType::RkwInt,
+                &TokenType::RkwString,
+            ],
+            message,
+        )?;
+        return Ok(DataType::get_token_data_type(token).unwrap());
+    }
+
+    fn default_value(&self, data_type: &DataType) -> Box<dyn Any> {
+        let token_type = match data_type {
+            DataType::INT => TokenType::RkwInt,
+            DataType::FLOAT => TokenType::RkwFloat,
+            DataType::CHAR => TokenType::RkwChar,
+            DataType::BOOL => TokenType::RkwBool,
+            DataType::STR => TokenType::RkwString,
+            DataType::ARRAY(data_type, length) => {
+                return Box::new(
+                    (0..*length)
+                        .map(|_| self.default_value(data_type))
+                        .collect::<Vec<Box<dyn Any>>>(),
+                )
+            }
+            DataType::RECORD(name) => {
+                return Box::new(Record {
+                    name: name.clone(),
+                    fields: self.record_types[name]
+                        .iter()
+                        .map(|(field, data_type)| (field.clone(), self.default_value(data_type)))
+                        .collect(),
+                })
+            }
+        };
+        return DataType::get_default_of_type(&token_type).unwrap();
+    }
+
+    fn is_declared(&self, name: &str) -> bool {
+        return self.scopes.last().unwrap().contains_key(name) || self.constants.contains_key(name);
+    }
+
+    fn expect_not_constant(&self, name: &Token) -> Result<(), String> {
+        if self.constants.contains_key(&name.lexeme) {
+            return Err(self.source_code.error_string_token(
+                name,
+                &format!("Cannot assign to constant '{}'.", name.lexeme),
+            ));
+        }
+        return Ok(());
+    }
+
     fn declaration(&mut self) -> Result<Box<dyn Statement>, String> {
         return if self.compare_then_next(&[&TokenType::RkwVar]) {
             self.variable_declaration()
+        } else if self.compare_then_next(&[&TokenType::RkwConst]) {
+            self.constant_declaration()
+        } else if self.compare_then_next(&[&TokenType::RkwType]) {
+            self.type_declaration()
+        } else if self.compare_then_next(&[&TokenType::RkwFunction, &TokenType::RkwSub]) {
+            self.function_declaration()
+        } else if self.compare_then_next(&[&TokenType::RkwTest]) {
+            self.test_section()
         } else {
             self.statement()
         };
@@ -187,7 +430,7 @@ impl Parser<'_> {
         if !self.declaring {
             self.declaring = true;
         }
-        if !self.var_declarations {
+        if !self.var_declarations && !self.block_declarations {
             return Err(self.source_code.error_string_token(
                 self.get_previous().unwrap(),
                 "Misplaced variable declaration.",
@@ -214,62 +457,113 @@ impl Parser<'_> {
         while !self.compare_then_next(&[&TokenType::RkwAs, &TokenType::Eol, &TokenType::RkwStart]) {
             self.current_index += 1;
         }
-        let token_type = if self.compare_then_next(&[
+        let expect_data_type_error = "Expected declaration variable data type.";
+        let data_type = if self.compare_current(&TokenType::Identifier) {
+            self.data_type(expect_data_type_error)?
+        } else if self.compare_then_next(&[
             &TokenType::RkwBool,
             &TokenType::RkwChar,
             &TokenType::RkwFloat,
             &TokenType::RkwInt,
+            &TokenType::RkwString,
         ]) {
-            self.get_previous().unwrap().clone()
+            DataType::get_token_data_type(self.get_previous().unwrap()).unwrap()
         } else {
             return Err(self
                 .source_code
-                .error_string_token(&name, "Expected declaration variable data type."));
+                .error_string_token(&name, expect_data_type_error));
+        };
+        let range = if self.compare_then_next(&[&TokenType::RkwRange]) {
+            Some(self.range(&data_type)?)
+        } else {
+            None
         };
         self.current_index = temp_current_index;
 
         let mut variable_declarations = Vec::new();
 
         let mut push_declaration = |parser: &mut Parser, name: Token| {
-            if parser.variable_type.contains_key(&name.lexeme) {
+            let length = if parser.compare_then_next(&[&TokenType::SymLeftBracket]) {
+                Some(parser.array_length()?)
+            } else {
+                None
+            };
+            if length.is_some() && range.is_some() {
+                return Err(parser
+                    .source_code
+                    .error_string_token(&name, "An array cannot have a RANGE."));
+            }
+            if parser.is_declared(&name.lexeme) {
                 return Err(parser.source_code.error_string_token(
                     &name,
                     &format!("Variable name '{}' is already declared.", &name.lexeme),
                 ));
             } else {
-                parser.variable_type.insert(
+                parser.scopes.last_mut().unwrap().insert(
                     name.lexeme.clone(),
-                    DataType::get_token_data_type(&token_type).unwrap(),
+                    match length {
+                        Some(length) => DataType::ARRAY(Box::new(data_type.clone()), length),
+                        None => data_type.clone(),
+                    },
                 );
             }
 
-            let mut initializer;
-            if parser.compare_then_next(&[&TokenType::SymAssignment]) {
-                initializer = parser.expression()?;
+            let mut initializer: Box<dyn Expression>;
+            if let Some(length) = length {
+                if parser.compare_current(&TokenType::SymAssignment) {
+                    return Err(parser.source_code.error_string_token(
+                        parser.get_current(),
+                        "An array cannot have an initializer.",
+                    ));
+                }
+                initializer = Box::new(Literal {
+                    value: parser
+                        .default_value(&DataType::ARRAY(Box::new(data_type.clone()), length)),
+                });
+            } else if let DataType::RECORD(_) = data_type {
+                if parser.compare_current(&TokenType::SymAssignment) {
+                    return Err(parser.source_code.error_string_token(
+                        parser.get_current(),
+                        "A record cannot have an initializer.",
+                    ));
+                }
+                initializer = Box::new(Literal {
+                    value: parser.default_value(&data_type),
+                });
+            } else if parser.compare_then_next(&[&TokenType::SymAssignment]) {
+                initializer = Parser::bool_literal_as_str(&data_type, parser.expression()?);
                 if let Some(literal) = (*initializer).as_any().downcast_ref::<Literal>() {
                     let value_data_type = DataType::box_any_to_data_type(&literal.value).unwrap();
-                    if token_type.token_type == TokenType::RkwFloat
-                        && value_data_type == DataType::INT
-                    {
+                    if data_type == DataType::FLOAT && value_data_type == DataType::INT {
                         let value = *DataType::downcast_box_any::<i32>(&literal.value).unwrap();
                         initializer = Box::new(Literal {
-                            value: Box::new(value),
+                            value: Box::new(f64::from(value)),
                         });
-                    } else if value_data_type != DataType::get_token_data_type(&token_type).unwrap()
-                    {
-                        return Err(parser.source_code.error_string_token(
-                            &name,
-                            &format!("Expected {} type.", token_type.lexeme),
-                        ));
+                    } else if value_data_type != data_type {
+                        return Err(parser
+                            .source_code
+                            .error_string_token(&name, &format!("Expected {} type.", data_type)));
                     }
                 }
             } else {
                 initializer = Box::new(Literal {
-                    value: Box::new(DataType::get_default_of_type(&token_type.token_type).unwrap()),
+                    value: parser.default_value(&data_type),
                 });
             }
 
-            variable_declarations.push(Var { name, initializer });
+            if let (Some(range), Some(literal)) =
+                (&range, initializer.as_any().downcast_ref::<Literal>())
+            {
+                range
+                    .check(&name.lexeme, &literal.value)
+                    .map_err(|error| parser.source_code.error_string_token(&name, &error))?;
+            }
+
+            variable_declarations.push(Var {
+                name,
+                initializer,
+                range: range.clone(),
+            });
             return Ok(());
         };
 
@@ -280,17 +574,11 @@ impl Parser<'_> {
             push_declaration(self, name)?;
         }
 
-        let expect_data_type_error = "Expected declaration variable data type.";
         self.expect_then_next(&[&TokenType::RkwAs], expect_data_type_error)?;
-        self.expect_then_next(
-            &[
-                &TokenType::RkwBool,
-                &TokenType::RkwChar,
-                &TokenType::RkwFloat,
-                &TokenType::RkwInt,
-            ],
-            expect_data_type_error,
-        )?;
+        self.data_type(expect_data_type_error)?;
+        if self.compare_then_next(&[&TokenType::RkwRange]) {
+            self.range(&data_type)?;
+        }
         self.expect_then_next(&[&TokenType::Eol], "Expected new line after declaration.")?;
 
         if self.declaring {
@@ -302,6 +590,339 @@ impl Parser<'_> {
         }));
     }
 
+    fn range(&mut self, data_type: &DataType) -> Result<Range, String> {
+        let keyword = self.get_previous().unwrap().clone();
+        if !matches!(data_type, DataType::INT | DataType::FLOAT) {
+            return Err(self.source_code.error_string_token(
+                &keyword,
+                &format!(
+                    "Expected INT or FLOAT type for RANGE but got {}.",
+                    data_type
+                ),
+            ));
+        }
+        let low = self.range_bound(data_type)?;
+        self.expect_then_next(&[&TokenType::RkwTo], "Expected 'TO' after the RANGE start.")?;
+        let high = self.range_bound(data_type)?;
+        if low > high {
+            return Err(self.source_code.error_string_token(
+                &keyword,
+                &format!("The RANGE start {} is greater than its end {}.", low, high),
+            ));
+        }
+
+        return Ok(Range { low, high });
+    }
+
+    fn range_bound(&mut self, data_type: &DataType) -> Result<f64, String> {
+        let token = self.get_current().clone();
+        let bound = self.concatenation()?;
+        let value = match bound.visit(&mut Environment::new(Options::default())) {
+            Ok(value) => value,
+            Err(_) => {
+                return Err(self
+                    .source_code
+                    .error_string_token(&token, "Expected a constant expression."))
+            }
+        };
+        let value = DataType::coerce(value, data_type)
+            .map_err(|error| self.source_code.error_string_token(&token, &error))?;
+        return Ok(match DataType::downcast_box_any::<i32>(&value) {
+            Some(value) => f64::from(*value),
+            None => *DataType::downcast_box_any::<f64>(&value).unwrap(),
+        });
+    }
+
+    fn constant_declaration(&mut self) -> Result<Box<dyn Statement>, String> {
+        let keyword = self.get_previous().unwrap().clone();
+        if !self.var_declarations || self.current_function.is_some() {
+            return Err(self
+                .source_code
+                .error_string_token(&keyword, "Misplaced constant declaration."));
+        }
+
+        let mut declarations = Vec::new();
+        loop {
+            let name = if self.compare_then_next(&[&TokenType::Identifier]) {
+                self.get_previous().unwrap().clone()
+            } else if TokenType::is_reserved_keyword(&self.get_current().token_type) {
+                return Err(self.source_code.error_string_token(
+                    self.get_current(),
+                    "Expected valid constant name but got reserved keyword.",
+                ));
+            } else {
+                return Err(self
+                    .source_code
+                    .error_string_token(self.get_current(), "Expected valid constant name."));
+            };
+            if self.is_declared(&name.lexeme)
+                || declarations
+                    .iter()
+                    .any(|(other, _, _): &(Token, Token, _)| other.lexeme == name.lexeme)
+            {
+                return Err(self.source_code.error_string_token(
+                    &name,
+                    &format!("Constant name '{}' is already declared.", name.lexeme),
+                ));
+            }
+            self.expect_then_next(
+                &[&TokenType::SymAssignment],
+                "Expected '=' and the constant value.",
+            )?;
+            let token = self.get_current().clone();
+            let initializer = self.expression()?;
+            declarations.push((name, token, initializer));
+            if !self.compare_then_next(&[&TokenType::SymComma]) {
+                break;
+            }
+        }
+
+        let expect_data_type_error = "Expected declaration constant data type.";
+        self.expect_then_next(&[&TokenType::RkwAs], expect_data_type_error)?;
+        let data_type = self
+            .expect_then_next(
+                &[
+                    &TokenType::RkwBool,
+                    &TokenType::RkwChar,
+                    &TokenType::RkwFloat,
+                    &TokenType::RkwInt,
+                    &TokenType::RkwString,
+                ],
+                expect_data_type_error,
+            )?
+            .clone();
+        let data_type = DataType::get_token_data_type(&data_type).unwrap();
+        self.expect_then_next(&[&TokenType::Eol], "Expected new line after declaration.")?;
+
+        let mut environment = Environment::new(Options::default());
+        let mut constants = Vec::new();
+        for (name, token, initializer) in declarations {
+            let initializer = Parser::bool_literal_as_str(&data_type, initializer);
+            let value = match initializer.visit(&mut environment) {
+                Ok(value) => value,
+                Err(_) => {
+                    return Err(self
+                        .source_code
+                        .error_string_token(&token, "Expected a constant expression."))
+                }
+            };
+            let value = DataType::coerce(value, &data_type)
+                .map_err(|error| self.source_code.error_string_token(&token, &error))?;
+            self.constants.insert(
+                name.lexeme.clone(),
+                DataType::clone_ref_any(&value).unwrap(),
+            );
+            constants.push((name, value));
+        }
+
+        return Ok(Box::new(ConstDec { constants }));
+    }
+
+    fn type_declaration(&mut self) -> Result<Box<dyn Statement>, String> {
+        let keyword = self.get_previous().unwrap().clone();
+        if !self.var_declarations || self.current_function.is_some() {
+            return Err(self
+                .source_code
+                .error_string_token(&keyword, "Misplaced type declaration."));
+        }
+        let name = self
+            .expect_then_next(&[&TokenType::Identifier], "Expected valid type name.")?
+            .clone();
+        if self.record_types.contains_key(&name.lexeme) {
+            return Err(self.source_code.error_string_token(
+                &name,
+                &format!("Type name '{}' is already declared.", name.lexeme),
+            ));
+        }
+        self.expect_then_next(&[&TokenType::Eol], "Expected new line after type name.")?;
+
+        let mut fields: Vec<(Token, DataType)> = Vec::new();
+        while !self.compare_then_next(&[&TokenType::RkwEnd]) {
+            let mut names = Vec::new();
+            loop {
+                let field = self
+                    .expect_then_next(
+                        &[&TokenType::Identifier],
+                        "Expected a field name or 'END TYPE'.",
+                    )?
+                    .clone();
+                if fields
+                    .iter()
+                    .map(|(other, _)| other)
+                    .chain(names.iter())
+                    .any(|other| other.lexeme == field.lexeme)
+                {
+                    return Err(self.source_code.error_string_token(
+                        &field,
+                        &format!("Field name '{}' is already declared.", field.lexeme),
+                    ));
+                }
+                names.push(field);
+                if !self.compare_then_next(&[&TokenType::SymComma]) {
+                    break;
+                }
+            }
+            self.expect_then_next(&[&TokenType::RkwAs], "Expected 'AS' after field name.")?;
+            let data_type = self.data_type("Expected field data type.")?;
+            self.expect_then_next(&[&TokenType::Eol], "Expected new line after field.")?;
+            fields.extend(names.into_iter().map(|field| (field, data_type.clone())));
+        }
+        if fields.is_empty() {
+            return Err(self.source_code.error_string_token(
+                &name,
+                &format!("Expected at least one field in type '{}'.", name.lexeme),
+            ));
+        }
+        self.expect_then_next(&[&TokenType::RkwType], "Expected 'TYPE' after 'END'.")?;
+        self.expect_then_next(&[&TokenType::Eol], "Expected new line after 'END TYPE'.")?;
+
+        self.record_types.insert(
+            name.lexeme.clone(),
+            fields
+                .iter()
+                .map(|(field, data_type)| (field.lexeme.clone(), data_type.clone()))
+                .collect(),
+        );
+        return Ok(Box::new(TypeDec { name, fields }));
+    }
+
+    fn function_declaration(&mut self) -> Result<Box<dyn Statement>, String> {
+        let keyword = self.get_previous().unwrap().clone();
+        if !self.var_declarations || self.current_function.is_some() {
+            return Err(self
+                .source_code
+                .error_string_token(&keyword, "Misplaced function declaration."));
+        }
+        let name = self
+            .expect_then_next(
+                &[&TokenType::Identifier],
+                &format!("Expected valid {} name.", keyword.lexeme),
+            )?
+            .clone();
+        if builtin::get(&name.lexeme).is_some() {
+            return Err(self
+                .source_code
+                .error_string_token(&name, &format!("'{}' is a built-in function.", name.lexeme)));
+        }
+        if self.functions.contains_key(&name.lexeme) {
+            return Err(self.source_code.error_string_token(
+                &name,
+                &format!("Function name '{}' is already declared.", name.lexeme),
+            ));
+        }
+        self.expect_then_next(
+            &[&TokenType::SymLeftParenthesis],
+            &format!("Expected '(' after {} name.", keyword.lexeme),
+        )?;
+        let mut parameters: Vec<(Token, DataType)> = Vec::new();
+        if !self.compare_then_next(&[&TokenType::SymRightParenthesis]) {
+            loop {
+                let parameter = self
+                    .expect_then_next(&[&TokenType::Identifier], "Expected parameter name.")?
+                    .clone();
+                if self.constants.contains_key(&parameter.lexeme)
+                    || parameters
+                        .iter()
+                        .any(|(other, _)| other.lexeme == parameter.lexeme)
+                {
+                    return Err(self.source_code.error_string_token(
+                        &parameter,
+                        &format!("Parameter name '{}' is already declared.", parameter.lexeme),
+                    ));
+                }
+                self.expect_then_next(&[&TokenType::RkwAs], "Expected 'AS' after parameter name.")?;
+                let data_type = self.data_type("Expected parameter data type.")?;
+                parameters.push((parameter, data_type));
+                if !self.compare_then_next(&[&TokenType::SymComma]) {
+                    break;
+                }
+            }
+            self.expect_then_next(
+                &[&TokenType::SymRightParenthesis],
+                "Expected ')' after parameters.",
+            )?;
+        }
+        let return_type = if keyword.token_type == TokenType::RkwFunction {
+            self.expect_then_next(
+                &[&TokenType::RkwAs],
+                "Expected 'AS' and the return data type after ')'.",
+            )?;
+            Some(self.data_type("Expected return data type.")?)
+        } else {
+            None
+        };
+        self.expect_then_next(
+            &[&TokenType::Eol],
+            &format!("Expected new line after {} header.", keyword.lexeme),
+        )?;
+        self.functions.insert(
+            name.lexeme.clone(),
+            FunctionSignature {
+                parameters: parameters
+                    .iter()
+                    .map(|(_, data_type)| data_type.clone())
+                    .collect(),
+                return_type: return_type.clone(),
+            },
+        );
+
+        let scopes = mem::replace(
+            &mut self.scopes,
+            vec![parameters
+                .iter()
+                .map(|(name, data_type)| (name.lexeme.clone(), data_type.clone()))
+                .collect()],
+        );
+        let in_scope = self.in_scope;
+        let scope_counter = self.scope_counter;
+        let loop_depth = self.loop_depth;
+        self.in_scope = false;
+        self.scope_counter = 0;
+        self.loop_depth = 0;
+        self.current_function = Some(name.clone());
+
+        let mut declarations = Vec::new();
+        while self.compare_then_next(&[&TokenType::RkwVar]) {
+            declarations.push(self.variable_declaration()?);
+        }
+        self.expect_then_next(
+            &[&TokenType::RkwStart],
+            &format!("Expected 'START' before {} body.", keyword.lexeme),
+        )?;
+        let body = self.block()?;
+
+        self.scopes = scopes;
+        self.var_declarations = true;
+        self.in_scope = in_scope;
+        self.scope_counter = scope_counter;
+        self.loop_depth = loop_depth;
+        self.current_function = None;
+
+        return Ok(Box::new(Function {
+            callable: Rc::new(Callable {
+                name,
+                parameters,
+                return_type,
+                declarations,
+                body,
+            }),
+        }));
+    }
+
+    fn array_length(&mut self) -> Result<usize, String> {
+        let error = "Expected a positive INT array length.";
+        let token = self.expect_then_next(&[&TokenType::LitInt], error)?.clone();
+        let length = match token.lexeme.trim().parse::<usize>() {
+            Ok(length) if length > 0 => length,
+            _ => return Err(self.source_code.error_string_token(&token, error)),
+        };
+        self.expect_then_next(
+            &[&TokenType::SymRightBracket],
+            "Expected ']' after array length.",
+        )?;
+        return Ok(length);
+    }
+
     fn statement(&mut self) -> Result<Box<dyn Statement>, String> {
         if self.compare_then_next(&[&TokenType::RkwStart]) {
             return self.block();
@@ -319,6 +940,29 @@ impl Parser<'_> {
             return self.input();
         } else if self.compare_then_next(&[&TokenType::RkwWhile]) {
             return self.while_stmt();
+        } else if self.compare_then_next(&[&TokenType::RkwFor]) {
+            return self.for_stmt();
+        } else if self.compare_then_next(&[&TokenType::RkwBreak, &TokenType::RkwContinue]) {
+            return self.loop_control();
+        } else if self.compare_then_next(&[&TokenType::RkwSwitch]) {
+            return self.switch_stmt();
+        } else if self.compare_then_next(&[&TokenType::RkwReturn]) {
+            return self.return_stmt();
+        } else if self.compare_then_next(&[&TokenType::RkwTry]) {
+            return self.try_stmt();
+        } else if self.compare_then_next(&[&TokenType::RkwThrow]) {
+            return self.throw_stmt();
+        } else if self.compare_then_next(&[&TokenType::RkwAssert]) {
+            return self.assert_stmt();
+        } else if self.compare_current(&TokenType::Identifier)
+            && self.tokens[self.current_index + 1].token_type == TokenType::SymLeftParenthesis
+            && self
+                .functions
+                .get(&self.get_current().lexeme)
+                .is_some_and(|function| function.return_type.is_none())
+        {
+            self.next();
+            return self.sub_call();
         }
 
         return self.expression_statement();
@@ -331,7 +975,13 @@ impl Parser<'_> {
                 "Out of scope expression is only allowed in variable declaration.",
             ));
         }
-        let expression = self.expression()?;
+        let mut expression = self.expression()?;
+        if self.compare_then_next(&[&TokenType::SymIncrement, &TokenType::SymDecrement]) {
+            let operator = self.get_previous().unwrap().clone();
+            self.expect_numeric_target(&operator, &*expression)?;
+            let one = Box::new(Literal { value: Box::new(1) });
+            expression = self.compound_assign_to(operator, expression, one)?;
+        }
         self.expect_then_next(&[&TokenType::Eol], "Expected new line after expression.")?;
 
         return Ok(Box::new(statement::expression::Expression {
@@ -348,26 +998,21 @@ impl Parser<'_> {
         return if self.compare_then_next(&[&TokenType::SymAssignment]) {
             let equals = self.get_previous().unwrap().clone();
             let value = self.assignment()?;
-            if let Some(expression) = (*expression).as_any().downcast_ref::<Variable>() {
-                let name = expression.name.to_owned();
-                let data_type = self.variable_type.get(&name.lexeme).unwrap().clone();
-                if let Some(value) = (*value).as_any().downcast_ref::<Literal>() {
-                    if DataType::box_any_to_data_type(&value.value).unwrap() != data_type {
-                        return Err(self.source_code.error_string_token(
-                            &name,
-                            &format!("Expected {:?} type.", data_type),
-                        ));
-                    }
-                }
-                return Ok(Box::new(Assign {
-                    name,
-                    value,
-                    data_type,
-                }));
+            self.assign_to(equals, expression, value)
+        } else if self.compare_then_next(&[
+            &TokenType::SymPlusAssignment,
+            &TokenType::SymMinusAssignment,
+            &TokenType::SymStarAssignment,
+            &TokenType::SymForwardSlashAssignment,
+            &TokenType::SymPercentAssignment,
+            &TokenType::SymAmpersandAssignment,
+        ]) {
+            let operator = self.get_previous().unwrap().clone();
+            if operator.token_type != TokenType::SymAmpersandAssignment {
+                self.expect_numeric_target(&operator, &*expression)?;
             }
-            Err(self
-                .source_code
-                .error_string_token(&equals, "Invalid assignment target."))
+            let right = self.assignment()?;
+            self.compound_assign_to(operator, expression, right)
         } else if self.compare_then_next(&[
             &TokenType::LitBool,
             &TokenType::LitChar,
@@ -384,6 +1029,143 @@ impl Parser<'_> {
         };
     }
 
+    fn expect_numeric_target(
+        &self,
+        operator: &Token,
+        expression: &dyn Expression,
+    ) -> Result<(), String> {
+        let data_type = self.expression_data_type(expression);
+        if !matches!(data_type, Some(DataType::INT) | Some(DataType::FLOAT)) {
+            return Err(self.source_code.error_string_token(
+                operator,
+                &format!("Expected INT or FLOAT type for {}.", operator.lexeme),
+            ));
+        }
+        return Ok(());
+    }
+
+    fn binary_operator(operator: &Token) -> Token {
+        let (token_type, lexeme) = match operator.token_type {
+            TokenType::SymPlusAssignment | TokenType::SymIncrement => (TokenType::SymPlus, "+"),
+            TokenType::SymMinusAssignment | TokenType::SymDecrement => (TokenType::SymMinus, "-"),
+            TokenType::SymStarAssignment => (TokenType::SymStar, "*"),
+            TokenType::SymForwardSlashAssignment => (TokenType::SymForwardSlash, "/"),
+            TokenType::SymPercentAssignment => (TokenType::SymPercent, "%"),
+            _ => (TokenType::SymAmpersand, "&"),
+        };
+        let mut operator = operator.clone();
+        operator.token_type = token_type;
+        operator.lexeme = lexeme.to_owned();
+        return operator;
+    }
+
+    // The value is type checked as `<target> <operator> <right>`, with a
+    // literal of the target's type standing in for the target.
+    fn compound_assign_to(
+        &mut self,
+        operator: Token,
+        target: Box<dyn Expression>,
+        right: Box<dyn Expression>,
+    ) -> Result<Box<dyn Expression>, String> {
+        let token = if let Some(target) = (*target).as_any().downcast_ref::<Variable>() {
+            target.name.clone()
+        } else if let Some(target) = (*target).as_any().downcast_ref::<Index>() {
+            target.name.clone()
+        } else if let Some(target) = (*target).as_any().downcast_ref::<Get>() {
+            target.name.clone()
+        } else {
+            return Err(self
+                .source_code
+                .error_string_token(&operator, "Invalid assignment target."));
+        };
+        let data_type = self.target_data_type(&*target)?;
+        let current: Box<dyn Any> = match data_type {
+            DataType::INT => Box::new(0),
+            DataType::FLOAT => Box::new(0.0),
+            _ => Box::new(String::new()),
+        };
+        let value = Binary {
+            operator: Parser::binary_operator(&operator),
+            left: Box::new(Literal { value: current }),
+            right,
+        };
+        self.expect_data_type(&operator, &data_type, &value)?;
+        return Ok(Box::new(CompoundAssign {
+            token,
+            operator: value.operator,
+            target,
+            value: value.right,
+            data_type,
+        }));
+    }
+
+    // Names that are not declared yet are only accepted by `primary` while
+    // parsing the declarations, so an assignment to one is reported here.
+    fn target_data_type(&self, target: &dyn Expression) -> Result<DataType, String> {
+        if let Some(data_type) = self.expression_data_type(target) {
+            return Ok(data_type);
+        }
+        let mut target = target;
+        while let Some(get) = target.as_any().downcast_ref::<Get>() {
+            target = &*get.object;
+        }
+        let name = if let Some(target) = target.as_any().downcast_ref::<Variable>() {
+            &target.name
+        } else if let Some(target) = target.as_any().downcast_ref::<Index>() {
+            &target.name
+        } else {
+            return Err("Invalid assignment target.".to_owned());
+        };
+        return Err(self
+            .source_code
+            .error_string_token(name, &format!("Undefined variable {}.", name.lexeme)));
+    }
+
+    fn assign_to(
+        &mut self,
+        equals: Token,
+        expression: Box<dyn Expression>,
+        value: Box<dyn Expression>,
+    ) -> Result<Box<dyn Expression>, String> {
+        if let Some(expression) = (*expression).as_any().downcast_ref::<Variable>() {
+            let name = expression.name.to_owned();
+            let data_type = self.target_data_type(expression)?;
+            let value = Parser::bool_literal_as_str(&data_type, value);
+            if let Some(value) = (*value).as_any().downcast_ref::<Literal>() {
+                let value_data_type = DataType::box_any_to_data_type(&value.value).unwrap();
+                if !DataType::is_assignable(&data_type, &value_data_type) {
+                    return Err(self
+                        .source_code
+                        .error_string_token(&name, &format!("Expected {} type.", data_type)));
+                }
+            }
+            self.expect_data_type(&equals, &data_type, &*value)?;
+            return Ok(Box::new(Assign {
+                name,
+                value,
+                data_type,
+            }));
+        }
+        let token = if let Some(target) = (*expression).as_any().downcast_ref::<Index>() {
+            target.name.clone()
+        } else if let Some(target) = (*expression).as_any().downcast_ref::<Get>() {
+            target.name.clone()
+        } else {
+            return Err(self
+                .source_code
+                .error_string_token(&equals, "Invalid assignment target."));
+        };
+        let data_type = self.target_data_type(&*expression)?;
+        let value = Parser::bool_literal_as_str(&data_type, value);
+        self.expect_data_type(&equals, &data_type, &*value)?;
+        return Ok(Box::new(Set {
+            token,
+            target: expression,
+            value,
+            data_type,
+        }));
+    }
+
     fn concatenation(&mut self) -> Result<Box<dyn Expression>, String> {
         let mut expression = self.logical_or()?;
         while self.compare_then_next(&[&TokenType::SymAmpersand]) {
@@ -447,7 +1229,7 @@ impl Parser<'_> {
     }
 
     fn comparison(&mut self) -> Result<Box<dyn Expression>, String> {
-        let mut expression = self.term()?;
+        let mut expression = self.bitwise_or()?;
         while self.compare_then_next(&[
             &TokenType::SymLesser,
             &TokenType::SymLesserEqual,
@@ -455,7 +1237,7 @@ impl Parser<'_> {
             &TokenType::SymGreaterEqual,
         ]) {
             let operator = self.get_previous().unwrap().clone();
-            let right = self.term()?;
+            let right = self.bitwise_or()?;
             expression = Box::new(Binary {
                 operator,
                 right,
@@ -466,14 +1248,97 @@ impl Parser<'_> {
         return Ok(expression);
     }
 
-    fn term(&mut self) -> Result<Box<dyn Expression>, String> {
-        let mut expression = self.factor()?;
-        while self.compare_then_next(&[&TokenType::SymPlus, &TokenType::SymMinus]) {
-            let operator = self.get_previous().unwrap().clone();
-            let right = self.factor()?;
-            expression = Box::new(Binary {
-                operator,
-                right,
+    fn expect_int_operand(&self, operator: &Token, operand: &dyn Expression) -> Result<(), String> {
+        if let Some(data_type) = self.expression_data_type(operand) {
+            if data_type != DataType::INT {
+                return Err(self.source_code.error_string_token(
+                    operator,
+                    &format!(
+                        "Expected INT type for {} but got {}.",
+                        operator.lexeme, data_type
+                    ),
+                ));
+            }
+        }
+        return Ok(());
+    }
+
+    fn bitwise_or(&mut self) -> Result<Box<dyn Expression>, String> {
+        let mut expression = self.bitwise_xor()?;
+        while self.compare_then_next(&[&TokenType::RkwBor]) {
+            let operator = self.get_previous().unwrap().clone();
+            let right = self.bitwise_xor()?;
+            self.expect_int_operand(&operator, &*expression)?;
+            self.expect_int_operand(&operator, &*right)?;
+            expression = Box::new(Binary {
+                operator,
+                right,
+                left: expression,
+            });
+        }
+
+        return Ok(expression);
+    }
+
+    fn bitwise_xor(&mut self) -> Result<Box<dyn Expression>, String> {
+        let mut expression = self.bitwise_and()?;
+        while self.compare_then_next(&[&TokenType::RkwBxor]) {
+            let operator = self.get_previous().unwrap().clone();
+            let right = self.bitwise_and()?;
+            self.expect_int_operand(&operator, &*expression)?;
+            self.expect_int_operand(&operator, &*right)?;
+            expression = Box::new(Binary {
+                operator,
+                right,
+                left: expression,
+            });
+        }
+
+        return Ok(expression);
+    }
+
+    fn bitwise_and(&mut self) -> Result<Box<dyn Expression>, String> {
+        let mut expression = self.shift()?;
+        while self.compare_then_next(&[&TokenType::RkwBand]) {
+            let operator = self.get_previous().unwrap().clone();
+            let right = self.shift()?;
+            self.expect_int_operand(&operator, &*expression)?;
+            self.expect_int_operand(&operator, &*right)?;
+            expression = Box::new(Binary {
+                operator,
+                right,
+                left: expression,
+            });
+        }
+
+        return Ok(expression);
+    }
+
+    fn shift(&mut self) -> Result<Box<dyn Expression>, String> {
+        let mut expression = self.term()?;
+        while self.compare_then_next(&[&TokenType::RkwShl, &TokenType::RkwShr]) {
+            let operator = self.get_previous().unwrap().clone();
+            let right = self.term()?;
+            self.expect_int_operand(&operator, &*expression)?;
+            self.expect_int_operand(&operator, &*right)?;
+            expression = Box::new(Binary {
+                operator,
+                right,
+                left: expression,
+            });
+        }
+
+        return Ok(expression);
+    }
+
+    fn term(&mut self) -> Result<Box<dyn Expression>, String> {
+        let mut expression = self.factor()?;
+        while self.compare_then_next(&[&TokenType::SymPlus, &TokenType::SymMinus]) {
+            let operator = self.get_previous().unwrap().clone();
+            let right = self.factor()?;
+            expression = Box::new(Binary {
+                operator,
+                right,
                 left: expression,
             });
         }
@@ -505,16 +1370,94 @@ impl Parser<'_> {
             &TokenType::SymPlus,
             &TokenType::SymMinus,
             &TokenType::RkwNot,
+            &TokenType::RkwBnot,
         ]) {
             let operator = self.get_previous().unwrap().clone();
             let right = self.unary()?;
             if operator.token_type == TokenType::RkwNot {
                 self.expect_logical_expressions(&*right)?;
+            } else if operator.token_type == TokenType::RkwBnot {
+                self.expect_int_operand(&operator, &*right)?;
             }
             return Ok(Box::new(Unary { operator, right }));
         }
 
-        return self.primary();
+        return self.power();
+    }
+
+    fn power(&mut self) -> Result<Box<dyn Expression>, String> {
+        let expression = self.access()?;
+        if self.compare_then_next(&[&TokenType::SymPower]) {
+            let operator = self.get_previous().unwrap().clone();
+            // The exponent goes back through unary so that `2 ^ -1` and
+            // `2 ^ 3 ^ 2` (read as `2 ^ (3 ^ 2)`) are both accepted.
+            let right = self.unary()?;
+            self.expect_number_operand(&operator, &*expression)?;
+            self.expect_number_operand(&operator, &*right)?;
+            return Ok(Box::new(Binary {
+                operator,
+                left: expression,
+                right,
+            }));
+        }
+
+        return Ok(expression);
+    }
+
+    fn expect_number_operand(
+        &self,
+        operator: &Token,
+        operand: &dyn Expression,
+    ) -> Result<(), String> {
+        if let Some(data_type) = self.expression_data_type(operand) {
+            if data_type != DataType::INT && data_type != DataType::FLOAT {
+                return Err(self.source_code.error_string_token(
+                    operator,
+                    &format!(
+                        "Expected INT or FLOAT type for {} but got {}.",
+                        operator.lexeme, data_type
+                    ),
+                ));
+            }
+        }
+        return Ok(());
+    }
+
+    fn access(&mut self) -> Result<Box<dyn Expression>, String> {
+        let expression = self.primary()?;
+        return self.fields(expression);
+    }
+
+    fn fields(
+        &mut self,
+        mut expression: Box<dyn Expression>,
+    ) -> Result<Box<dyn Expression>, String> {
+        while self.compare_then_next(&[&TokenType::SymDot]) {
+            let dot = self.get_previous().unwrap().clone();
+            let name = self
+                .expect_then_next(&[&TokenType::Identifier], "Expected field name after '.'.")?
+                .clone();
+            match self.expression_data_type(&*expression) {
+                Some(DataType::RECORD(record)) => {
+                    if self.field_type(&record, &name.lexeme).is_none() {
+                        return Err(self.source_code.error_string_token(
+                            &name,
+                            &format!("Type '{}' has no field '{}'.", record, name.lexeme),
+                        ));
+                    }
+                }
+                _ => {
+                    return Err(self
+                        .source_code
+                        .error_string_token(&dot, "Only a record has fields."))
+                }
+            }
+            expression = Box::new(Get {
+                object: expression,
+                name,
+            });
+        }
+        return Ok(expression);
     }
 
     fn primary(&mut self) -> Result<Box<dyn Expression>, String> {
@@ -536,14 +1479,64 @@ impl Parser<'_> {
                         .error_string_token(previous_token, "Expected a literal value."))
                 }
             }
+            TokenType::Identifier
+                if self.compare_current(&TokenType::SymLeftParenthesis)
+                    && builtin::get(&previous_token.lexeme).is_some() =>
+            {
+                let name = previous_token.clone();
+                let builtin = builtin::get(&name.lexeme).unwrap();
+                Ok(Box::new(self.builtin_call(name, builtin)?))
+            }
+            TokenType::Identifier if self.compare_current(&TokenType::SymLeftParenthesis) => {
+                let name = previous_token.clone();
+                let call = self.call(name)?;
+                if self.expression_data_type(&call).is_none() {
+                    Err(self.source_code.error_string_token(
+                        &call.name,
+                        &format!("SUB '{}' does not return a value.", call.name.lexeme),
+                    ))
+                } else {
+                    Ok(Box::new(call))
+                }
+            }
+            TokenType::Identifier if self.compare_current(&TokenType::SymLeftBracket) => {
+                let name = previous_token.clone();
+                Ok(Box::new(self.index(name)?))
+            }
+            TokenType::Identifier if self.constants.contains_key(&previous_token.lexeme) => {
+                // The constant is replaced by its value, so a write to it is
+                // rejected here while its name is still known.
+                if matches!(
+                    self.get_current().token_type,
+                    TokenType::SymAssignment
+                        | TokenType::SymPlusAssignment
+                        | TokenType::SymMinusAssignment
+                        | TokenType::SymStarAssignment
+                        | TokenType::SymForwardSlashAssignment
+                        | TokenType::SymPercentAssignment
+                        | TokenType::SymAmpersandAssignment
+                        | TokenType::SymIncrement
+                        | TokenType::SymDecrement
+                ) {
+                    self.expect_not_constant(previous_token)?;
+                }
+                let value = &self.constants[&previous_token.lexeme];
+                Ok(Box::new(Literal {
+                    value: DataType::clone_ref_any(value).unwrap(),
+                }))
+            }
             TokenType::Identifier => {
-                if !self.var_declarations
-                    && !self.variable_type.contains_key(&previous_token.lexeme)
-                {
+                if !self.var_declarations && self.variable_type(&previous_token.lexeme).is_none() {
                     Err(self.source_code.error_string_token(
                         previous_token,
                         &format!("Undefined variable {}.", previous_token.lexeme),
                     ))
+                } else if let Some(DataType::ARRAY(..)) = self.variable_type(&previous_token.lexeme)
+                {
+                    Err(self.source_code.error_string_token(
+                        previous_token,
+                        &format!("Expected an index for array '{}'.", previous_token.lexeme),
+                    ))
                 } else {
                     Ok(Box::new(Variable {
                         name: previous_token.clone(),
@@ -558,6 +1551,10 @@ impl Parser<'_> {
                 )?;
                 Ok(Box::new(Grouping { expression }))
             }
+            TokenType::RkwIif => {
+                let token = previous_token.clone();
+                Ok(Box::new(self.conditional(token)?))
+            }
             _ => {
                 let error_string = self
                     .source_code
@@ -567,8 +1564,271 @@ impl Parser<'_> {
         };
     }
 
+    fn conditional(&mut self, token: Token) -> Result<Conditional, String> {
+        self.expect_then_next(
+            &[&TokenType::SymLeftParenthesis],
+            "Expected '(' after 'IIF'.",
+        )?;
+        let condition_token = self.get_current().clone();
+        let condition = self.expression()?;
+        if let Some(data_type) = self.expression_data_type(&*condition) {
+            if data_type != DataType::BOOL {
+                return Err(self.source_code.error_string_token(
+                    &condition_token,
+                    &format!("Expected a BOOL condition for IIF but got {}.", data_type),
+                ));
+            }
+        }
+        self.expect_then_next(&[&TokenType::SymComma], "Expected ',' after condition.")?;
+        let mut then_branch = self.expression()?;
+        self.expect_then_next(&[&TokenType::SymComma], "Expected ',' after value.")?;
+        let mut else_branch = self.expression()?;
+        self.expect_then_next(
+            &[&TokenType::SymRightParenthesis],
+            "Expected ')' after value.",
+        )?;
+
+        let then_type = self.expression_data_type(&*then_branch);
+        let else_type = self.expression_data_type(&*else_branch);
+        if then_type == Some(DataType::STR) {
+            else_branch = Parser::bool_literal_as_str(&DataType::STR, else_branch);
+        } else if else_type == Some(DataType::STR) {
+            then_branch = Parser::bool_literal_as_str(&DataType::STR, then_branch);
+        }
+        let then_type = self.expression_data_type(&*then_branch);
+        let else_type = self.expression_data_type(&*else_branch);
+        let data_type = match (then_type, else_type) {
+            (Some(then_type), Some(else_type)) if then_type == else_type => Some(then_type),
+            (Some(DataType::INT), Some(DataType::FLOAT))
+            | (Some(DataType::FLOAT), Some(DataType::INT)) => Some(DataType::FLOAT),
+            (Some(then_type), Some(else_type)) => {
+                return Err(self.source_code.error_string_token(
+                    &token,
+                    &format!(
+                        "Expected IIF values of the same type but got {} and {}.",
+                        then_type, else_type
+                    ),
+                ))
+            }
+            _ => None,
+        };
+
+        return Ok(Conditional {
+            token,
+            condition,
+            then_branch,
+            else_branch,
+            data_type,
+        });
+    }
+
+    fn index(&mut self, name: Token) -> Result<Index, String> {
+        self.next();
+        match self.variable_type(&name.lexeme) {
+            Some(DataType::ARRAY(..)) => (),
+            Some(_) => {
+                return Err(self
+                    .source_code
+                    .error_string_token(&name, &format!("'{}' is not an array.", name.lexeme)))
+            }
+            None if self.constants.contains_key(&name.lexeme) => {
+                return Err(self
+                    .source_code
+                    .error_string_token(&name, &format!("'{}' is not an array.", name.lexeme)))
+            }
+            None => {
+                return Err(self
+                    .source_code
+                    .error_string_token(&name, &format!("Undefined variable {}.", name.lexeme)))
+            }
+        }
+        let token = self.get_current().clone();
+        let index = self.expression()?;
+        self.expect_data_type(&token, &DataType::INT, &*index)?;
+        self.expect_then_next(
+            &[&TokenType::SymRightBracket],
+            "Expected ']' after array index.",
+        )?;
+
+        return Ok(Index { name, index });
+    }
+
+    fn arguments(&mut self) -> Result<Arguments, String> {
+        self.next();
+        let mut arguments = Vec::new();
+        let mut argument_tokens = Vec::new();
+        if !self.compare_then_next(&[&TokenType::SymRightParenthesis]) {
+            loop {
+                argument_tokens.push(self.get_current().clone());
+                arguments.push(self.expression()?);
+                if !self.compare_then_next(&[&TokenType::SymComma]) {
+                    break;
+                }
+            }
+            self.expect_then_next(
+                &[&TokenType::SymRightParenthesis],
+                "Expected ')' after arguments.",
+            )?;
+        }
+        return Ok((argument_tokens, arguments));
+    }
+
+    fn builtin_call(
+        &mut self,
+        name: Token,
+        builtin: &'static Builtin,
+    ) -> Result<BuiltinCall, String> {
+        let (argument_tokens, arguments) = self.arguments()?;
+        if !builtin.accepts_arguments(arguments.len()) {
+            let expected = if builtin.variadic {
+                format!("at least {}", builtin.min_arguments())
+            } else if builtin.optional > 0 {
+                format!(
+                    "{} to {}",
+                    builtin.min_arguments(),
+                    builtin.parameters.len()
+                )
+            } else {
+                builtin.parameters.len().to_string()
+            };
+            return Err(self.source_code.error_string_token(
+                &name,
+                &format!(
+                    "Expected {} argument(s) but got {}.",
+                    expected,
+                    arguments.len()
+                ),
+            ));
+        }
+        let mut data_types = Vec::new();
+        for (index, (token, argument)) in argument_tokens.iter().zip(&arguments).enumerate() {
+            let data_type = self.expression_data_type(&**argument);
+            if let Some(data_type) = &data_type {
+                let parameter = builtin.parameter(index);
+                if !parameter.accepts(data_type) {
+                    return Err(self.source_code.error_string_token(
+                        token,
+                        &format!("Expected {} type but got {}.", parameter, data_type),
+                    ));
+                }
+            }
+            data_types.push(data_type);
+        }
+        if builtin.name == "FORMAT" {
+            if let Some(literal) = arguments[0].as_any().downcast_ref::<Literal>() {
+                if let Some(format) = DataType::downcast_box_any::<String>(&literal.value) {
+                    builtin::check_format(format, arguments.len() - 1).map_err(|error| {
+                        self.source_code
+                            .error_string_token(&argument_tokens[0], &error)
+                    })?;
+                }
+            }
+        }
+
+        return Ok(BuiltinCall {
+            name,
+            builtin,
+            arguments,
+            data_type: builtin.return_type(&data_types),
+        });
+    }
+
+    fn call(&mut self, name: Token) -> Result<Call, String> {
+        let signature = match self.functions.get(&name.lexeme) {
+            Some(signature) => signature.clone(),
+            None => {
+                return Err(self
+                    .source_code
+                    .error_string_token(&name, &format!("Undefined function '{}'.", name.lexeme)))
+            }
+        };
+        let (argument_tokens, arguments) = self.arguments()?;
+        if arguments.len() != signature.parameters.len() {
+            return Err(self.source_code.error_string_token(
+                &name,
+                &format!(
+                    "Expected {} argument(s) but got {}.",
+                    signature.parameters.len(),
+                    arguments.len()
+                ),
+            ));
+        }
+        for ((token, argument), data_type) in argument_tokens
+            .iter()
+            .zip(&arguments)
+            .zip(&signature.parameters)
+        {
+            self.expect_data_type(token, data_type, &**argument)?;
+        }
+
+        return Ok(Call { name, arguments });
+    }
+
+    fn sub_call(&mut self) -> Result<Box<dyn Statement>, String> {
+        let name = self.get_previous().unwrap().clone();
+        let call = self.call(name)?;
+        self.expect_then_next(&[&TokenType::Eol], "Expected new line after expression.")?;
+
+        return Ok(Box::new(SubCall { call }));
+    }
+
+    fn return_stmt(&mut self) -> Result<Box<dyn Statement>, String> {
+        let token = self.get_previous().unwrap().clone();
+        let data_type = match &self.current_function {
+            Some(name) => self
+                .functions
+                .get(&name.lexeme)
+                .unwrap()
+                .return_type
+                .clone(),
+            None => {
+                return Err(self.source_code.error_string_token(
+                    &token,
+                    "'RETURN' is only allowed inside a FUNCTION or SUB.",
+                ))
+            }
+        };
+        let value = match &data_type {
+            Some(data_type) => {
+                let value_token = self.get_current().clone();
+                let value = self.expression()?;
+                self.expect_data_type(&value_token, data_type, &*value)?;
+                Some(value)
+            }
+            None => None,
+        };
+        self.expect_then_next(&[&TokenType::Eol], "Expected new line after 'RETURN'.")?;
+
+        return Ok(Box::new(Return {
+            token,
+            value,
+            data_type,
+        }));
+    }
+
     fn if_stmt(&mut self) -> Result<Box<dyn Statement>, String> {
         let if_token = self.get_previous().unwrap().clone();
+        let mut branches = vec![self.if_branch()?];
+        let mut else_branch = None;
+        while self.compare_then_next(&[&TokenType::RkwElse]) {
+            if self.compare_then_next(&[&TokenType::RkwIf]) {
+                branches.push(self.if_branch()?);
+                continue;
+            }
+            self.expect_then_next(&[&TokenType::Eol], "Expected new line after 'ELSE'.")?;
+            self.expect_token_and_eol(&TokenType::RkwStart, "Expected 'START' before code block.")?;
+            else_branch = Some(self.statement()?);
+            break;
+        }
+
+        return Ok(Box::new(If {
+            token: if_token,
+            branches,
+            else_branch,
+        }));
+    }
+
+    fn if_branch(&mut self) -> Result<Branch, String> {
         self.expect_then_next(
             &[&TokenType::SymLeftParenthesis],
             "Expected '(' after 'if'.",
@@ -579,23 +1839,9 @@ impl Parser<'_> {
             "Expected ')' after condition.",
         )?;
         self.expect_token_and_eol(&TokenType::RkwStart, "Expected 'START' before code block.")?;
-        self.in_control_structure = true;
-        let then_branch = self.statement()?;
-        let else_branch = if self.compare_then_next(&[&TokenType::RkwElse]) {
-            self.expect_then_next(&[&TokenType::Eol], "Expected new line after 'ELSE'.")?;
-            self.expect_token_and_eol(&TokenType::RkwStart, "Expected 'START' before code block.")?;
-            self.in_control_structure = true;
-            Some(self.statement()?)
-        } else {
-            None
-        };
+        let branch = self.statement()?;
 
-        return Ok(Box::new(If {
-            token: if_token,
-            condition,
-            then_branch,
-            else_branch,
-        }));
+        return Ok((condition, branch));
     }
 
     fn output(&mut self) -> Result<Box<dyn Statement>, String> {
@@ -608,20 +1854,56 @@ impl Parser<'_> {
 
     fn input(&mut self) -> Result<Box<dyn Statement>, String> {
         self.expect_then_next(&[&TokenType::SymColon], "Expected ':' after 'INPUT'.")?;
-        let name = self
-            .expect_then_next(
-                &[&TokenType::Identifier],
-                "Expected a variable name to receive the input.",
-            )?
-            .clone();
+        let mut variables = Vec::new();
+        loop {
+            let name = self
+                .expect_then_next(
+                    &[&TokenType::Identifier],
+                    "Expected a variable name to receive the input.",
+                )?
+                .clone();
+            self.expect_not_constant(&name)?;
+            let target: Box<dyn Expression> = if self.compare_current(&TokenType::SymLeftBracket) {
+                Box::new(self.index(name)?)
+            } else {
+                Box::new(Variable { name })
+            };
+            let target = self.fields(target)?;
+            let data_type = match self.expression_data_type(&*target) {
+                Some(DataType::ARRAY(..)) => {
+                    return Err(self.source_code.error_string_token(
+                        self.get_previous().unwrap(),
+                        "Expected an index for the array to receive the input.",
+                    ))
+                }
+                Some(DataType::RECORD(..)) => {
+                    return Err(self.source_code.error_string_token(
+                        self.get_previous().unwrap(),
+                        "Expected a field of the record to receive the input.",
+                    ))
+                }
+                Some(data_type) => data_type,
+                None => {
+                    return Err(self.source_code.error_string_token(
+                        self.get_previous().unwrap(),
+                        &format!(
+                            "Undefined variable {}.",
+                            self.get_previous().unwrap().lexeme
+                        ),
+                    ))
+                }
+            };
+            variables.push((target, data_type));
+            if !self.compare_then_next(&[&TokenType::SymComma]) {
+                break;
+            }
+        }
         self.expect_then_next(
             &[&TokenType::Eol],
-            "Expected only one variable to receive the input.",
+            "Expected ',' or new line after the input variable.",
         )?;
 
-        let variable = Variable { name };
-
-        return Ok(Box::new(Input { variable }));
+        return Ok(Box::new(Input { variables }));
     }
 
     fn while_stmt(&mut self) -> Result<Box<dyn Statement>, String> {
@@ -635,18 +1917,330 @@ impl Parser<'_> {
             "Expected ')' after condition.",
         )?;
         self.expect_token_and_eol(&TokenType::RkwStart, "Expected 'START' before code block.")?;
-        self.in_control_structure = true;
+        self.loop_depth += 1;
         let body = self.statement()?;
+        self.loop_depth -= 1;
 
         return Ok(Box::new(While { condition, body }));
     }
 
-    fn block(&mut self) -> Result<Box<dyn Statement>, String> {
-        if self.in_scope && !self.in_control_structure {
-            return Err(self
+    fn for_stmt(&mut self) -> Result<Box<dyn Statement>, String> {
+        let for_token = self.get_previous().unwrap().clone();
+        self.expect_then_next(
+            &[&TokenType::SymLeftParenthesis],
+            "Expected '(' after 'FOR'.",
+        )?;
+        let counter = self
+            .expect_then_next(
+                &[&TokenType::Identifier],
+                "Expected a loop counter variable.",
+            )?
+            .clone();
+        self.expect_not_constant(&counter)?;
+        let data_type = match self.variable_type(&counter.lexeme) {
+            Some(data_type) if *data_type == DataType::INT || *data_type == DataType::FLOAT => {
+                data_type.clone()
+            }
+            _ => {
+                return Err(self.source_code.error_string_token(
+                    &counter,
+                    "Expected a declared INT or FLOAT variable as the loop counter.",
+                ))
+            }
+        };
+        self.expect_then_next(
+            &[&TokenType::SymAssignment],
+            "Expected '=' after the loop counter.",
+        )?;
+        let start = self.for_range_value(&data_type)?;
+        self.expect_then_next(
+            &[&TokenType::RkwTo],
+            "Expected 'TO' after the initial value.",
+        )?;
+        let end = self.for_range_value(&data_type)?;
+        let step = if self.compare_then_next(&[&TokenType::RkwStep]) {
+            Some(self.for_range_value(&data_type)?)
+        } else {
+            None
+        };
+        self.expect_token_and_eol_next(
+            &TokenType::SymRightParenthesis,
+            "Expected ')' after the loop range.",
+        )?;
+        self.expect_token_and_eol(&TokenType::RkwStart, "Expected 'START' before code block.")?;
+        self.loop_depth += 1;
+        let body = self.statement()?;
+        self.loop_depth -= 1;
+
+        return Ok(Box::new(For {
+            token: for_token,
+            counter,
+            data_type,
+            start,
+            end,
+            step,
+            body,
+        }));
+    }
+
+    fn for_range_value(&mut self, data_type: &DataType) -> Result<Box<dyn Expression>, String> {
+        let token = self.get_current().clone();
+        let expression = self.expression()?;
+        let literal = match (*expression).as_any().downcast_ref::<Unary>() {
+            Some(unary) => (*unary.right).as_any().downcast_ref::<Literal>(),
+            None => (*expression).as_any().downcast_ref::<Literal>(),
+        };
+        if let Some(literal) = literal {
+            let value_data_type = DataType::box_any_to_data_type(&literal.value).unwrap();
+            if value_data_type != *data_type
+                && !(*data_type == DataType::FLOAT && value_data_type == DataType::INT)
+            {
+                return Err(self
+                    .source_code
+                    .error_string_token(&token, &format!("Expected {} type.", data_type)));
+            }
+        }
+        return Ok(expression);
+    }
+
+    fn try_stmt(&mut self) -> Result<Box<dyn Statement>, String> {
+        let token = self.get_previous().unwrap().clone();
+        self.expect_then_next(&[&TokenType::Eol], "Expected new line after 'TRY'.")?;
+        self.expect_token_and_eol(&TokenType::RkwStart, "Expected 'START' before code block.")?;
+        let body = self.statement()?;
+        self.expect_then_next(
+            &[&TokenType::RkwCatch],
+            "Expected 'CATCH' after the TRY block.",
+        )?;
+        let name = self
+            .expect_then_next(
+                &[&TokenType::Identifier],
+                "Expected a variable name to receive the error.",
+            )?
+            .clone();
+        self.expect_not_constant(&name)?;
+        self.expect_then_next(
+            &[&TokenType::Eol],
+            "Expected new line after the error variable.",
+        )?;
+        self.expect_token_and_eol(&TokenType::RkwStart, "Expected 'START' before code block.")?;
+        self.scopes.push(HashMap::from([(
+            name.lexeme.clone(),
+            DataType::RECORD("Error".to_owned()),
+        )]));
+        let handler = self.statement();
+        self.scopes.pop();
+
+        return Ok(Box::new(Try {
+            token,
+            body,
+            name,
+            handler: handler?,
+        }));
+    }
+
+    fn throw_stmt(&mut self) -> Result<Box<dyn Statement>, String> {
+        let token = self.get_previous().unwrap().clone();
+        let value = self.expression()?;
+        self.expect_then_next(&[&TokenType::Eol], "Expected new line after 'THROW'.")?;
+
+        return Ok(Box::new(Throw { token, value }));
+    }
+
+    fn assert_stmt(&mut self) -> Result<Box<dyn Statement>, String> {
+        let token = self.get_previous().unwrap().clone();
+        let left_parenthesis = self
+            .expect_then_next(
+                &[&TokenType::SymLeftParenthesis],
+                "Expected '(' after 'ASSERT'.",
+            )?
+            .clone();
+        let condition_token = self.get_current().clone();
+        let condition = self.expression()?;
+        if let Some(data_type) = self.expression_data_type(&*condition) {
+            if data_type != DataType::BOOL {
+                return Err(self.source_code.error_string_token(
+                    &condition_token,
+                    &format!(
+                        "Expected a BOOL condition for ASSERT but got {}.",
+                        data_type
+                    ),
+                ));
+            }
+        }
+        let right_parenthesis = self
+            .expect_then_next(
+                &[&TokenType::SymRightParenthesis],
+                "Expected ')' after condition.",
+            )?
+            .clone();
+        let message = if self.compare_then_next(&[&TokenType::SymComma]) {
+            Some(self.expression()?)
+        } else {
+            None
+        };
+        self.expect_then_next(&[&TokenType::Eol], "Expected new line after 'ASSERT'.")?;
+
+        return Ok(Box::new(Assert {
+            token,
+            condition,
+            text: self
                 .source_code
-                .error_string_token(self.get_previous().unwrap(), "Nested scope in invalid."));
+                .code_between(&left_parenthesis, &right_parenthesis),
+            message,
+        }));
+    }
+
+    fn test_section(&mut self) -> Result<Box<dyn Statement>, String> {
+        let token = self.get_previous().unwrap().clone();
+        if self.in_scope || self.scope_counter == 0 || self.current_function.is_some() {
+            return Err(self.source_code.error_string_token(
+                &token,
+                "TEST is only allowed after the main START and STOP block.",
+            ));
+        }
+        let name = self
+            .expect_then_next(
+                &[&TokenType::LitStr],
+                "Expected the test name as a string after 'TEST'.",
+            )?
+            .lexeme
+            .clone();
+        self.expect_then_next(&[&TokenType::Eol], "Expected new line after the test name.")?;
+        self.expect_token_and_eol(&TokenType::RkwStart, "Expected 'START' before code block.")?;
+        self.in_scope = true;
+        let body = self.statement();
+        self.in_scope = false;
+
+        return Ok(Box::new(Test {
+            token,
+            name,
+            body: body?,
+        }));
+    }
+
+    fn loop_control(&mut self) -> Result<Box<dyn Statement>, String> {
+        let token = self.get_previous().unwrap().clone();
+        if self.loop_depth == 0 {
+            return Err(self.source_code.error_string_token(
+                &token,
+                &format!("'{}' is only allowed inside a loop.", token.lexeme),
+            ));
+        }
+        self.expect_then_next(
+            &[&TokenType::Eol],
+            &format!("Expected new line after '{}'.", token.lexeme),
+        )?;
+
+        return Ok(match token.token_type {
+            TokenType::RkwBreak => Box::new(Break { token }),
+            _ => Box::new(Continue { token }),
+        });
+    }
+
+    fn switch_stmt(&mut self) -> Result<Box<dyn Statement>, String> {
+        let switch_token = self.get_previous().unwrap().clone();
+        self.expect_then_next(
+            &[&TokenType::SymLeftParenthesis],
+            "Expected '(' after 'SWITCH'.",
+        )?;
+        let scrutinee_token = self.get_current().clone();
+        let scrutinee = self.expression()?;
+        self.expect_token_and_eol_next(
+            &TokenType::SymRightParenthesis,
+            "Expected ')' after expression.",
+        )?;
+        let mut data_type = self.expression_data_type(&*scrutinee);
+        if let Some(data_type) = &data_type {
+            if !matches!(data_type, DataType::INT | DataType::CHAR | DataType::BOOL) {
+                return Err(self.source_code.error_string_token(
+                    &scrutinee_token,
+                    &format!("Expected INT, CHAR or BOOL type but got {}.", data_type),
+                ));
+            }
         }
+
+        let mut cases = Vec::new();
+        let mut seen: Vec<Box<dyn Any>> = Vec::new();
+        self.expect_then_next(&[&TokenType::RkwCase], "Expected 'CASE' after 'SWITCH'.")?;
+        loop {
+            let mut literals = Vec::new();
+            loop {
+                let token = self.get_current().clone();
+                let literal = self.case_literal()?;
+                let literal_data_type = DataType::box_any_to_data_type(&literal);
+                if data_type.is_none() {
+                    data_type = literal_data_type.clone();
+                }
+                if data_type != literal_data_type {
+                    return Err(self.source_code.error_string_token(
+                        &token,
+                        &format!("Expected {} type.", data_type.unwrap()),
+                    ));
+                }
+                if seen
+                    .iter()
+                    .any(|other| DataType::is_equal(other, &literal).unwrap_or(false))
+                {
+                    return Err(self
+                        .source_code
+                        .error_string_token(&token, "Duplicate CASE value."));
+                }
+                seen.push(DataType::clone_ref_any(&literal).unwrap());
+                literals.push(literal);
+                if !self.compare_then_next(&[&TokenType::SymComma]) {
+                    break;
+                }
+            }
+            self.expect_then_next(&[&TokenType::Eol], "Expected new line after CASE values.")?;
+            self.expect_token_and_eol(&TokenType::RkwStart, "Expected 'START' before code block.")?;
+            cases.push((literals, self.statement()?));
+            if !self.compare_then_next(&[&TokenType::RkwCase]) {
+                break;
+            }
+        }
+        let default = if self.compare_then_next(&[&TokenType::RkwDefault]) {
+            self.expect_then_next(&[&TokenType::Eol], "Expected new line after 'DEFAULT'.")?;
+            self.expect_token_and_eol(&TokenType::RkwStart, "Expected 'START' before code block.")?;
+            Some(self.statement()?)
+        } else {
+            None
+        };
+
+        return Ok(Box::new(Switch {
+            token: switch_token,
+            scrutinee,
+            cases,
+            default,
+        }));
+    }
+
+    fn case_literal(&mut self) -> Result<Box<dyn Any>, String> {
+        let is_negative = if self.compare_then_next(&[&TokenType::SymPlus, &TokenType::SymMinus]) {
+            let sign = self.get_previous().unwrap().token_type.clone();
+            if !self.compare_current(&TokenType::LitInt) {
+                return Err(self
+                    .source_code
+                    .error_string_token(self.get_current(), "Expected INT literal after sign."));
+            }
+            sign == TokenType::SymMinus
+        } else {
+            false
+        };
+        let token = self
+            .expect_then_next(
+                &[&TokenType::LitInt, &TokenType::LitChar, &TokenType::LitBool],
+                "Expected an INT, CHAR or BOOL literal as CASE value.",
+            )?
+            .clone();
+        let value = DataType::str_to_data_type(&token.lexeme, &token.token_type).unwrap();
+        if is_negative {
+            return Ok(Box::new(-*value.downcast_ref::<i32>().unwrap()));
+        }
+        return Ok(value);
+    }
+
+    fn block(&mut self) -> Result<Box<dyn Statement>, String> {
         if !self.in_scope && self.scope_counter > 0 {
             return Err(self
                 .source_code
@@ -662,10 +2256,16 @@ impl Parser<'_> {
 
         let mut statements = vec![];
         self.expect_then_next(&[&TokenType::Eol], "Missing new line after 'START'.")?;
-        self.in_control_structure = false;
+        self.scopes.push(HashMap::new());
+        self.block_declarations = true;
+        while self.compare_then_next(&[&TokenType::RkwVar]) {
+            statements.push(self.variable_declaration()?);
+        }
+        self.block_declarations = false;
         while !self.compare_current(&TokenType::RkwStop) && !self.is_at_end() {
             statements.push(self.declaration()?);
         }
+        self.scopes.pop();
         self.expect_then_next(&[&TokenType::RkwStop], "Expected 'STOP' after code block.")?;
         if !self.is_at_end() {
             self.expect_then_next(&[&TokenType::Eol], "Missing new line after 'STOP'.")?;
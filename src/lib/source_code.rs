@@ -3,9 +3,31 @@ use super::token;
 pub struct SourceCode {
     pub source_code: String,
     pub vec: Vec<char>,
+    // Shown before the line in diagnostics, the main program has none.
+    pub file_name: Option<String>,
+    pub includes: Vec<SourceCode>,
 }
 
 impl SourceCode {
+    pub fn new(source_code: String, file_name: Option<String>) -> SourceCode {
+        SourceCode {
+            vec: source_code.chars().collect(),
+            source_code,
+            file_name,
+            includes: Vec::new(),
+        }
+    }
+
+    fn source_of(&self, token: &token::Token) -> Option<&SourceCode> {
+        if token.file.as_deref() == self.file_name.as_deref() {
+            return Some(self);
+        }
+        return self
+            .includes
+            .iter()
+            .find_map(|source_code| source_code.source_of(token));
+    }
+
     pub fn get_code_at_line(&self, line_number: usize) -> String {
         let mut start: usize = 0;
         let mut end: usize = 0;
@@ -29,17 +51,41 @@ impl SourceCode {
         String::from(&self.source_code[start..end])
     }
 
+    // The code between two tokens, up to the end of the line when they are on
+    // different lines.
+    pub fn code_between(&self, start: &token::Token, end: &token::Token) -> String {
+        let line_code: Vec<char> = self
+            .source_of(start)
+            .unwrap_or(self)
+            .get_code_at_line(start.line)
+            .chars()
+            .collect();
+        let first = (start.column + 1).min(line_code.len());
+        let last = if end.line == start.line {
+            end.column.clamp(first, line_code.len())
+        } else {
+            line_code.len()
+        };
+        return line_code[first..last]
+            .iter()
+            .collect::<String>()
+            .trim()
+            .to_owned();
+    }
+
     pub fn error_string_token(&self, token: &token::Token, message: &str) -> String {
-        let line_code = self.get_code_at_line(token.line as usize);
+        let line_code = self
+            .source_of(token)
+            .unwrap_or(self)
+            .get_code_at_line(token.line as usize);
         let mut error_point = " ".repeat(if token.column > 0 {
             token.column as usize - 1
         } else {
             token.column as usize
         });
         error_point.push('^');
-        let error_line = token.line + 1;
-        let error_column = token.column + 1;
-        format!("{message}\nline-{error_line}:column-{error_column}: {token}\n{line_code}\n{error_point}")
+        let location = token.location();
+        format!("{message}\n{location}: {token}\n{line_code}\n{error_point}")
     }
 
     pub fn error_string_manual(
@@ -54,8 +100,12 @@ impl SourceCode {
         error_point.push('^');
         let error_line = line + 1;
         let error_column = column + 1;
+        let file = match &self.file_name {
+            Some(file_name) => format!("{file_name}:"),
+            None => String::new(),
+        };
         format!(
-            "{message}\nline-{error_line}:column-{error_column}: {}\n{line_code}\n{error_point}",
+            "{message}\n{file}line-{error_line}:column-{error_column}: {}\n{line_code}\n{error_point}",
             at_fault.escape_debug()
         )
     }
@@ -3,21 +3,44 @@ use std::any::Any;
 use crate::environment::Environment;
 
 use self::{
-    block::Block, expression::Expression, if_stmt::If, input::Input, print::Print, var::Var,
-    var_dec::VarDec, while_stmt::While,
+    assert_stmt::Assert, block::Block, break_stmt::Break, const_dec::ConstDec,
+    continue_stmt::Continue, expression::Expression, for_stmt::For, function::Function,
+    if_stmt::If, input::Input, print::Print, return_stmt::Return, sub_call::SubCall,
+    switch_stmt::Switch, test_stmt::Test, throw_stmt::Throw, try_stmt::Try, type_dec::TypeDec,
+    var::Var, var_dec::VarDec, while_stmt::While,
 };
 
+pub mod assert_stmt;
 pub mod block;
+pub mod break_stmt;
+pub mod const_dec;
+pub mod continue_stmt;
 pub mod expression;
+pub mod for_stmt;
+pub mod function;
 pub mod if_stmt;
 pub mod input;
 pub mod print;
+pub mod return_stmt;
+pub mod sub_call;
+pub mod switch_stmt;
+pub mod test_stmt;
+pub mod throw_stmt;
+pub mod try_stmt;
+pub mod type_dec;
 pub mod var;
 pub mod var_dec;
 pub mod while_stmt;
 
+pub enum ControlFlow {
+    Next,
+    Break,
+    Continue,
+    Return(Option<Box<dyn Any>>),
+}
+
 pub trait Statement {
-    fn visit(&self, environment: &mut Environment) -> Result<(), String>;
+    fn visit(&self, environment: &mut Environment) -> Result<ControlFlow, String>;
     fn as_any(&self) -> &dyn Any;
 }
 
@@ -34,10 +57,36 @@ pub fn display_statement(statement: &Box<dyn Statement>) -> String {
         return statement.to_string();
     } else if let Some(statement) = (*statement).as_any().downcast_ref::<VarDec>() {
         return statement.to_string();
+    } else if let Some(statement) = (*statement).as_any().downcast_ref::<TypeDec>() {
+        return statement.to_string();
+    } else if let Some(statement) = (*statement).as_any().downcast_ref::<ConstDec>() {
+        return statement.to_string();
     } else if let Some(statement) = (*statement).as_any().downcast_ref::<Var>() {
         return statement.to_string();
     } else if let Some(statement) = (*statement).as_any().downcast_ref::<While>() {
         return statement.to_string();
+    } else if let Some(statement) = (*statement).as_any().downcast_ref::<For>() {
+        return statement.to_string();
+    } else if let Some(statement) = (*statement).as_any().downcast_ref::<Break>() {
+        return statement.to_string();
+    } else if let Some(statement) = (*statement).as_any().downcast_ref::<Continue>() {
+        return statement.to_string();
+    } else if let Some(statement) = (*statement).as_any().downcast_ref::<Switch>() {
+        return statement.to_string();
+    } else if let Some(statement) = (*statement).as_any().downcast_ref::<Function>() {
+        return statement.to_string();
+    } else if let Some(statement) = (*statement).as_any().downcast_ref::<Return>() {
+        return statement.to_string();
+    } else if let Some(statement) = (*statement).as_any().downcast_ref::<SubCall>() {
+        return statement.to_string();
+    } else if let Some(statement) = (*statement).as_any().downcast_ref::<Try>() {
+        return statement.to_string();
+    } else if let Some(statement) = (*statement).as_any().downcast_ref::<Throw>() {
+        return statement.to_string();
+    } else if let Some(statement) = (*statement).as_any().downcast_ref::<Assert>() {
+        return statement.to_string();
+    } else if let Some(statement) = (*statement).as_any().downcast_ref::<Test>() {
+        return statement.to_string();
     }
     return "".to_owned();
 }
@@ -0,0 +1,64 @@
+use std::{any::Any, fmt::Display};
+
+use crate::{
+    data_type::DataType,
+    environment::Environment,
+    expression::{display_expression, Expression},
+    interpreter::error_string_token,
+    token::Token,
+};
+
+use super::{ControlFlow, Statement};
+
+pub struct Assert {
+    pub token: Token,
+    pub condition: Box<dyn Expression>,
+    // The source code of the condition, shown when the assertion fails.
+    pub text: String,
+    pub message: Option<Box<dyn Expression>>,
+}
+
+impl Statement for Assert {
+    fn visit(&self, environment: &mut Environment) -> Result<ControlFlow, String> {
+        let value = match DataType::any_to_bool(&self.condition.visit(environment)?) {
+            Some(value) => *value,
+            None => {
+                return Err(error_string_token(
+                    &self.token,
+                    "Expected a BOOL condition for ASSERT.",
+                ))
+            }
+        };
+        if value {
+            return Ok(ControlFlow::Next);
+        }
+        let message = match &self.message {
+            Some(message) => format!(
+                "Assertion failed ({}): {}",
+                self.text,
+                DataType::stringify_primitives(&message.visit(environment)?)?
+            ),
+            None => format!("Assertion failed ({}).", self.text),
+        };
+        environment.error_kind = Some("ASSERT");
+        return Err(error_string_token(&self.token, &message));
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl Display for Assert {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Assert({}, {})",
+            display_expression(&self.condition),
+            match &self.message {
+                Some(message) => display_expression(message),
+                None => "None".to_owned(),
+            }
+        )
+    }
+}
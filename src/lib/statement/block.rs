@@ -2,18 +2,30 @@ use std::{any::Any, fmt::Display};
 
 use crate::environment::Environment;
 
-use super::{display_statement, Statement};
+use super::{display_statement, ControlFlow, Statement};
 
 pub struct Block {
     pub statements: Vec<Box<dyn Statement>>,
 }
 
-impl Statement for Block {
-    fn visit(&self, environment: &mut Environment) -> Result<(), String> {
+impl Block {
+    fn run(&self, environment: &mut Environment) -> Result<ControlFlow, String> {
         for statement in &self.statements {
-            statement.visit(environment)?;
+            let flow = statement.visit(environment)?;
+            if !matches!(flow, ControlFlow::Next) {
+                return Ok(flow);
+            }
         }
-        return Ok(());
+        return Ok(ControlFlow::Next);
+    }
+}
+
+impl Statement for Block {
+    fn visit(&self, environment: &mut Environment) -> Result<ControlFlow, String> {
+        environment.begin_scope();
+        let result = self.run(environment);
+        environment.end_scope();
+        return result;
     }
 
     fn as_any(&self) -> &dyn Any {
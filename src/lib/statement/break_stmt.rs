@@ -0,0 +1,25 @@
+use std::{any::Any, fmt::Display};
+
+use crate::{environment::Environment, token::Token};
+
+use super::{ControlFlow, Statement};
+
+pub struct Break {
+    pub token: Token,
+}
+
+impl Statement for Break {
+    fn visit(&self, _: &mut Environment) -> Result<ControlFlow, String> {
+        return Ok(ControlFlow::Break);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl Display for Break {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Break({:?})", self.token)
+    }
+}
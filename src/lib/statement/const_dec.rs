@@ -0,0 +1,35 @@
+use std::{any::Any, fmt::Display};
+
+use crate::{data_type::DataType, environment::Environment, token::Token};
+
+use super::{ControlFlow, Statement};
+
+pub struct ConstDec {
+    pub constants: Vec<(Token, Box<dyn Any>)>,
+}
+
+impl Statement for ConstDec {
+    fn visit(&self, _environment: &mut Environment) -> Result<ControlFlow, String> {
+        // Constants are inlined by the parser, so there is nothing to define at runtime.
+        return Ok(ControlFlow::Next);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl Display for ConstDec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut result = "ConstDec(\n".to_owned();
+        for (name, value) in &self.constants {
+            result.push_str(&format!(
+                "\tConst({}, {})\n",
+                name,
+                DataType::stringify_primitives(value).unwrap_or_default()
+            ));
+        }
+        result.push(')');
+        write!(f, "{}", result)
+    }
+}
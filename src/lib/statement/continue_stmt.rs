@@ -0,0 +1,25 @@
+use std::{any::Any, fmt::Display};
+
+use crate::{environment::Environment, token::Token};
+
+use super::{ControlFlow, Statement};
+
+pub struct Continue {
+    pub token: Token,
+}
+
+impl Statement for Continue {
+    fn visit(&self, _: &mut Environment) -> Result<ControlFlow, String> {
+        return Ok(ControlFlow::Continue);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl Display for Continue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Continue({:?})", self.token)
+    }
+}
@@ -5,16 +5,16 @@ use crate::{
     expression::{self, display_expression},
 };
 
-use super::Statement;
+use super::{ControlFlow, Statement};
 
 pub struct Expression {
     pub statement: Box<dyn expression::Expression>,
 }
 
 impl Statement for Expression {
-    fn visit(&self, environment: &mut Environment) -> Result<(), String> {
+    fn visit(&self, environment: &mut Environment) -> Result<ControlFlow, String> {
         self.statement.visit(environment)?;
-        return Ok(());
+        return Ok(ControlFlow::Next);
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -0,0 +1,139 @@
+use std::{any::Any, fmt::Display};
+
+use crate::{
+    data_type::DataType,
+    environment::Environment,
+    expression::{display_expression, Expression},
+    interpreter::error_string_token,
+    token::Token,
+};
+
+use super::{ControlFlow, Statement};
+
+pub struct For {
+    pub token: Token,
+    pub counter: Token,
+    pub data_type: DataType,
+    pub start: Box<dyn Expression>,
+    pub end: Box<dyn Expression>,
+    pub step: Option<Box<dyn Expression>>,
+    pub body: Box<dyn Statement>,
+}
+
+impl For {
+    fn range_value<T: Copy + 'static>(
+        &self,
+        value: Box<dyn Any>,
+        convert: fn(&Box<dyn Any>) -> Option<T>,
+    ) -> Result<T, String> {
+        return match convert(&value) {
+            Some(value) => Ok(value),
+            None => Err(error_string_token(
+                &self.token,
+                &format!(
+                    "Expected {} values for the loop counter '{}'.",
+                    self.data_type, self.counter.lexeme
+                ),
+            )),
+        };
+    }
+
+    fn run<T: Copy + PartialOrd + 'static>(
+        &self,
+        environment: &mut Environment,
+        convert: fn(&Box<dyn Any>) -> Option<T>,
+        one: T,
+        zero: T,
+        add: fn(T, T) -> Option<T>,
+    ) -> Result<ControlFlow, String> {
+        let start = self.range_value(self.start.visit(environment)?, convert)?;
+        let end = self.range_value(self.end.visit(environment)?, convert)?;
+        let step = match &self.step {
+            Some(step) => self.range_value(step.visit(environment)?, convert)?,
+            None => one,
+        };
+        if step == zero {
+            return Err(error_string_token(
+                &self.token,
+                "Expected a non-zero loop STEP.",
+            ));
+        }
+        let name = self.counter.lexeme.clone();
+        environment
+            .assign(name.clone(), Box::new(start))
+            .map_err(|error| error_string_token(&self.counter, &error))?;
+        loop {
+            let current = convert(environment.get(&name)?).unwrap();
+            if (step > zero && current > end) || (step < zero && current < end) {
+                break;
+            }
+            match self.body.visit(environment)? {
+                ControlFlow::Break => break,
+                ControlFlow::Return(value) => return Ok(ControlFlow::Return(value)),
+                _ => (),
+            }
+            let current = convert(environment.get(&name)?).unwrap();
+            let next = match add(current, step) {
+                Some(next) => next,
+                None => break,
+            };
+            // A counter with a RANGE that ends at <end> stops there instead of
+            // failing on the step past it.
+            let is_past_end = (step > zero && next > end) || (step < zero && next < end);
+            let next: Box<dyn Any> = Box::new(next);
+            if is_past_end && environment.check_range(&name, &next).is_err() {
+                break;
+            }
+            environment
+                .assign(name.clone(), next)
+                .map_err(|error| error_string_token(&self.counter, &error))?;
+        }
+        return Ok(ControlFlow::Next);
+    }
+}
+
+impl Statement for For {
+    fn visit(&self, environment: &mut Environment) -> Result<ControlFlow, String> {
+        return match self.data_type {
+            DataType::INT => self.run(
+                environment,
+                |value| DataType::downcast_box_any::<i32>(value).copied(),
+                1,
+                0,
+                i32::checked_add,
+            ),
+            _ => self.run(
+                environment,
+                |value| {
+                    if let Some(value) = DataType::downcast_box_any::<i32>(value) {
+                        return Some(f64::from(*value));
+                    }
+                    return DataType::downcast_box_any::<f64>(value).copied();
+                },
+                1.0,
+                0.0,
+                |current, step| Some(current + step),
+            ),
+        };
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl Display for For {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "For({:?}, {}, {}, {}, Statement)",
+            self.counter,
+            display_expression(&self.start),
+            display_expression(&self.end),
+            match &self.step {
+                Some(step) => display_expression(step),
+                None => "None".to_owned(),
+            }
+        )
+    }
+}
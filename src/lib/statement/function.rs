@@ -0,0 +1,116 @@
+use std::{any::Any, collections::HashMap, fmt::Display, mem, rc::Rc};
+
+use crate::{
+    data_type::DataType,
+    environment::{Environment, Scope},
+    interpreter::{error_string_token, is_stack_exhausted},
+    token::Token,
+};
+
+use super::{ControlFlow, Statement};
+
+pub struct Callable {
+    pub name: Token,
+    pub parameters: Vec<(Token, DataType)>,
+    pub return_type: Option<DataType>,
+    pub declarations: Vec<Box<dyn Statement>>,
+    pub body: Box<dyn Statement>,
+}
+
+impl Callable {
+    pub fn call(
+        &self,
+        environment: &mut Environment,
+        token: &Token,
+        arguments: Vec<Box<dyn Any>>,
+    ) -> Result<Option<Box<dyn Any>>, String> {
+        if environment.call_depth >= environment.options.max_call_depth {
+            return Err(error_string_token(
+                token,
+                &format!(
+                    "Maximum call depth of {} exceeded.",
+                    environment.options.max_call_depth
+                ),
+            ));
+        }
+        // Calls through large expressions can use up the stack before the
+        // maximum call depth is reached.
+        if is_stack_exhausted() {
+            return Err(error_string_token(
+                token,
+                &format!(
+                    "Out of stack space at call depth {}, the calls are nested too deeply.",
+                    environment.call_depth
+                ),
+            ));
+        }
+        let mut frame = HashMap::new();
+        for ((name, data_type), argument) in self.parameters.iter().zip(arguments) {
+            let value = DataType::coerce(argument, data_type).map_err(|error| {
+                error_string_token(
+                    token,
+                    &format!("Invalid argument for '{}'. {}", name.lexeme, error),
+                )
+            })?;
+            frame.insert(name.lexeme.clone(), value);
+        }
+
+        let caller = mem::replace(&mut environment.scope, Scope::new(frame));
+        environment.call_depth += 1;
+        let result = self.execute(environment);
+        environment.call_depth -= 1;
+        environment.scope = caller;
+
+        let value = match result? {
+            ControlFlow::Return(value) => value,
+            _ => None,
+        };
+        if self.return_type.is_some() && value.is_none() {
+            return Err(error_string_token(
+                &self.name,
+                &format!("FUNCTION '{}' ended without RETURN.", self.name.lexeme),
+            ));
+        }
+        return Ok(value);
+    }
+
+    fn execute(&self, environment: &mut Environment) -> Result<ControlFlow, String> {
+        for declaration in &self.declarations {
+            declaration.visit(environment)?;
+        }
+        return self.body.visit(environment);
+    }
+}
+
+pub struct Function {
+    pub callable: Rc<Callable>,
+}
+
+impl Statement for Function {
+    fn visit(&self, environment: &mut Environment) -> Result<ControlFlow, String> {
+        environment.define_function(self.callable.name.lexeme.clone(), Rc::clone(&self.callable));
+        return Ok(ControlFlow::Next);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl Display for Function {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let parameters: Vec<String> = self
+            .callable
+            .parameters
+            .iter()
+            .map(|(name, data_type)| format!("{} {:?}", name.lexeme, data_type))
+            .collect();
+        write!(
+            f,
+            "Function({:?}, [{}], {:?}, Statement)",
+            self.callable.name,
+            parameters.join(", "),
+            self.callable.return_type
+        )
+    }
+}
@@ -3,30 +3,34 @@ use std::{any::Any, fmt::Display};
 use crate::{
     environment::Environment,
     expression::{display_expression, Expression},
-    statement::Statement,
+    statement::{ControlFlow, Statement},
     token::Token,
 };
 
+pub type Branch = (Box<dyn Expression>, Box<dyn Statement>);
+
 pub struct If {
     pub token: Token,
-    pub condition: Box<dyn Expression>,
-    pub then_branch: Box<dyn Statement>,
+    pub branches: Vec<Branch>,
     pub else_branch: Option<Box<dyn Statement>>,
 }
 
 impl Statement for If {
-    fn visit(&self, environment: &mut Environment) -> Result<(), String> {
-        let any_value = self.condition.visit(environment)?;
-        if let Some(condition) = any_value.downcast_ref::<bool>() {
-            if *condition {
-                self.then_branch.visit(environment)?;
-            } else if let Some(else_branch) = &self.else_branch {
-                else_branch.visit(environment)?;
+    fn visit(&self, environment: &mut Environment) -> Result<ControlFlow, String> {
+        for (condition, branch) in &self.branches {
+            let any_value = condition.visit(environment)?;
+            if let Some(condition) = any_value.downcast_ref::<bool>() {
+                if *condition {
+                    return branch.visit(environment);
+                }
+            } else {
+                return Err("Invalid data type.".to_owned());
             }
-        } else {
-            return Err("Invalid data type.".to_owned());
         }
-        return Ok(());
+        if let Some(else_branch) = &self.else_branch {
+            return else_branch.visit(environment);
+        }
+        return Ok(ControlFlow::Next);
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -36,6 +40,17 @@ impl Statement for If {
 
 impl Display for If {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "If({})", display_expression(&self.condition))
+        let mut result = String::new();
+        for (index, (condition, _)) in self.branches.iter().enumerate() {
+            if index == 0 {
+                result.push_str(&format!("If({})", display_expression(condition)));
+            } else {
+                result.push_str(&format!(", ElseIf({})", display_expression(condition)));
+            }
+        }
+        if self.else_branch.is_some() {
+            result.push_str(", Else");
+        }
+        write!(f, "{}", result)
     }
 }
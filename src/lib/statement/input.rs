@@ -1,45 +1,137 @@
-use std::{any::Any, fmt::Display, io::stdin, str::FromStr};
+use std::{
+    any::Any,
+    fmt::Display,
+    io::{self, stdin, Write},
+};
 
-use crate::{data_type::DataType, environment::Environment, expression};
+use crate::{
+    data_type::DataType,
+    environment::Environment,
+    expression::{
+        display_expression, get::Get, index::Index, set::assign_target, variable::Variable,
+        Expression,
+    },
+};
 
-use super::Statement;
+use super::{ControlFlow, Statement};
 
 pub struct Input {
-    pub variable: expression::variable::Variable,
+    pub variables: Vec<(Box<dyn Expression>, DataType)>,
 }
 
-impl Statement for Input {
-    fn visit(&self, environment: &mut Environment) -> Result<(), String> {
-        let mut buf = String::new();
-        if stdin().read_line(&mut buf).is_err() {
-            return Err("Something went wrong while reading from stdin.".to_owned());
+impl Input {
+    fn target_name(target: &dyn Expression) -> String {
+        if let Some(target) = target.as_any().downcast_ref::<Index>() {
+            return format!("{}[]", target.name.lexeme);
+        } else if let Some(target) = target.as_any().downcast_ref::<Variable>() {
+            return target.name.lexeme.clone();
+        } else if let Some(target) = target.as_any().downcast_ref::<Get>() {
+            return format!(
+                "{}.{}",
+                Input::target_name(&*target.object),
+                target.name.lexeme
+            );
         }
-        let data_type = environment.data_type(&self.variable.name.lexeme)?;
-        let value = match data_type {
-            DataType::INT => handle_parse::<i32>(&buf),
-            DataType::FLOAT => handle_parse::<f64>(&buf),
-            DataType::CHAR => handle_parse::<char>(&buf),
-            DataType::BOOL => handle_parse::<bool>(&buf),
-            DataType::STR => return Err("Invalid STR data type.".to_owned()),
-        }?;
-        environment.assign(self.variable.name.lexeme.clone(), value)?;
-        return Ok(());
+        return "".to_owned();
     }
 
-    fn as_any(&self) -> &dyn Any {
-        self
+    fn parse_values(&self, line: &str) -> Result<Vec<Box<dyn Any>>, String> {
+        let line = line.trim_end_matches(['\r', '\n']);
+        let values: Vec<&str> = match self.variables.last() {
+            Some((_, DataType::STR)) => line.splitn(self.variables.len(), ',').collect(),
+            _ => line.split(',').collect(),
+        };
+        if values.len() != self.variables.len() {
+            return Err(format!(
+                "Expected {} comma-separated value(s) but got {}.",
+                self.variables.len(),
+                values.len()
+            ));
+        }
+        let mut result = Vec::new();
+        for ((variable, data_type), value) in self.variables.iter().zip(values) {
+            match DataType::parse_literal(value, data_type) {
+                Ok(value) => result.push(value),
+                Err(error) => {
+                    return Err(format!(
+                        "Invalid {} value '{}' for '{}'. {}",
+                        data_type,
+                        value.trim().escape_debug(),
+                        Input::target_name(&**variable),
+                        error
+                    ))
+                }
+            }
+        }
+        return Ok(result);
+    }
+
+    fn check_ranges(
+        &self,
+        environment: &Environment,
+        values: &[Box<dyn Any>],
+    ) -> Result<(), String> {
+        for ((variable, _), value) in self.variables.iter().zip(values) {
+            if let Some(variable) = variable.as_any().downcast_ref::<Variable>() {
+                environment.check_range(&variable.name.lexeme, value)?;
+            }
+        }
+        return Ok(());
     }
 }
 
-fn handle_parse<T: FromStr + 'static>(buf: &str) -> Result<Box<dyn Any>, String> {
-    match buf.trim().parse::<T>() {
-        Ok(value) => Ok(Box::new(value)),
-        Err(_) => Err("Something went wrong while parsing from stdin.".to_owned()),
+impl Statement for Input {
+    fn visit(&self, environment: &mut Environment) -> Result<ControlFlow, String> {
+        let values = loop {
+            let mut buf = String::new();
+            match stdin().read_line(&mut buf) {
+                Ok(0) => {
+                    environment.error_kind = Some("INPUT");
+                    return Err("Unexpected end of input.".to_owned());
+                }
+                Ok(_) => (),
+                Err(_) => {
+                    environment.error_kind = Some("INPUT");
+                    return Err("Something went wrong while reading from stdin.".to_owned());
+                }
+            }
+            // Every value is checked against its RANGE before any of them is
+            // assigned, so that a rejected line leaves all the variables as they were.
+            let values = self.parse_values(&buf).and_then(|values| {
+                self.check_ranges(environment, &values)?;
+                return Ok(values);
+            });
+            match values {
+                Ok(values) => break values,
+                Err(error) if environment.options.interactive => {
+                    print!("{error} Please try again: ");
+                    let _ = io::stdout().flush();
+                }
+                Err(error) => {
+                    environment.error_kind = Some("INPUT");
+                    return Err(error);
+                }
+            }
+        };
+        for ((variable, _), value) in self.variables.iter().zip(values) {
+            assign_target(&**variable, environment, value)?;
+        }
+        return Ok(ControlFlow::Next);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
     }
 }
 
 impl Display for Input {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Input({})", self.variable)
+        let variables = self
+            .variables
+            .iter()
+            .map(|(variable, _)| display_expression(variable))
+            .collect::<Vec<String>>()
+            .join(", ");
+        write!(f, "Input({})", variables)
     }
 }
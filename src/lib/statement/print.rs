@@ -8,7 +8,7 @@ use crate::{
     data_type::DataType,
     environment::Environment,
     expression::{display_expression, Expression},
-    statement::Statement,
+    statement::{ControlFlow, Statement},
 };
 
 pub struct Print {
@@ -16,12 +16,12 @@ pub struct Print {
 }
 
 impl Statement for Print {
-    fn visit(&self, environment: &mut Environment) -> Result<(), String> {
+    fn visit(&self, environment: &mut Environment) -> Result<ControlFlow, String> {
         let output = self.expression.visit(environment)?;
         let value = DataType::stringify_primitives(&output)?;
         print!("{value}");
         let _ = io::stdout().flush();
-        return Ok(());
+        return Ok(ControlFlow::Next);
     }
 
     fn as_any(&self) -> &dyn Any {
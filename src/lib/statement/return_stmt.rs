@@ -0,0 +1,47 @@
+use std::{any::Any, fmt::Display};
+
+use crate::{
+    data_type::DataType,
+    environment::Environment,
+    expression::{display_expression, Expression},
+    interpreter::error_string_token,
+    token::Token,
+};
+
+use super::{ControlFlow, Statement};
+
+pub struct Return {
+    pub token: Token,
+    pub value: Option<Box<dyn Expression>>,
+    pub data_type: Option<DataType>,
+}
+
+impl Statement for Return {
+    fn visit(&self, environment: &mut Environment) -> Result<ControlFlow, String> {
+        return match (&self.value, &self.data_type) {
+            (Some(value), Some(data_type)) => {
+                let value = DataType::coerce(value.visit(environment)?, data_type)
+                    .map_err(|error| error_string_token(&self.token, &error))?;
+                Ok(ControlFlow::Return(Some(value)))
+            }
+            _ => Ok(ControlFlow::Return(None)),
+        };
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl Display for Return {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Return({})",
+            match &self.value {
+                Some(value) => display_expression(value),
+                None => "None".to_owned(),
+            }
+        )
+    }
+}
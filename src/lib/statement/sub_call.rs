@@ -0,0 +1,26 @@
+use std::{any::Any, fmt::Display};
+
+use crate::{environment::Environment, expression::call::Call};
+
+use super::{ControlFlow, Statement};
+
+pub struct SubCall {
+    pub call: Call,
+}
+
+impl Statement for SubCall {
+    fn visit(&self, environment: &mut Environment) -> Result<ControlFlow, String> {
+        self.call.invoke(environment)?;
+        return Ok(ControlFlow::Next);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl Display for SubCall {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SubCall({})", self.call)
+    }
+}
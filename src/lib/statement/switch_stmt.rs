@@ -0,0 +1,62 @@
+use std::{any::Any, fmt::Display};
+
+use crate::{
+    data_type::DataType,
+    environment::Environment,
+    expression::{display_expression, Expression},
+    interpreter::error_string_token,
+    token::Token,
+};
+
+use super::{ControlFlow, Statement};
+
+pub type Case = (Vec<Box<dyn Any>>, Box<dyn Statement>);
+
+pub struct Switch {
+    pub token: Token,
+    pub scrutinee: Box<dyn Expression>,
+    pub cases: Vec<Case>,
+    pub default: Option<Box<dyn Statement>>,
+}
+
+impl Statement for Switch {
+    fn visit(&self, environment: &mut Environment) -> Result<ControlFlow, String> {
+        let value = self.scrutinee.visit(environment)?;
+        for (literals, body) in &self.cases {
+            for literal in literals {
+                let is_match = DataType::is_equal(&value, literal)
+                    .map_err(|error| error_string_token(&self.token, &error))?;
+                if is_match {
+                    return body.visit(environment);
+                }
+            }
+        }
+        if let Some(default) = &self.default {
+            return default.visit(environment);
+        }
+        return Ok(ControlFlow::Next);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl Display for Switch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut result = format!("Switch({}", display_expression(&self.scrutinee));
+        for (literals, _) in &self.cases {
+            let literals = literals
+                .iter()
+                .map(|literal| DataType::stringify_primitives(literal).unwrap_or_default())
+                .collect::<Vec<String>>()
+                .join(", ");
+            result.push_str(&format!(", Case({})", literals));
+        }
+        if self.default.is_some() {
+            result.push_str(", Default");
+        }
+        result.push(')');
+        write!(f, "{}", result)
+    }
+}
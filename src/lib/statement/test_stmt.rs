@@ -0,0 +1,36 @@
+use std::{any::Any, fmt::Display};
+
+use crate::{
+    environment::Environment,
+    statement::{display_statement, ControlFlow, Statement},
+    token::Token,
+};
+
+pub struct Test {
+    pub token: Token,
+    pub name: String,
+    pub body: Box<dyn Statement>,
+}
+
+impl Statement for Test {
+    // A TEST section is skipped when running the program, only
+    // `interpreter::run_tests` runs its body.
+    fn visit(&self, _environment: &mut Environment) -> Result<ControlFlow, String> {
+        return Ok(ControlFlow::Next);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl Display for Test {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Test({:?}, {})",
+            self.name,
+            display_statement(&self.body)
+        )
+    }
+}
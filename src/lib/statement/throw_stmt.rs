@@ -0,0 +1,39 @@
+use std::{any::Any, fmt::Display};
+
+use crate::{
+    data_type::DataType,
+    environment::Environment,
+    expression::{display_expression, Expression},
+    interpreter::error_string_token,
+    token::Token,
+};
+
+use super::{ControlFlow, Statement};
+
+pub struct Throw {
+    pub token: Token,
+    pub value: Box<dyn Expression>,
+}
+
+impl Statement for Throw {
+    fn visit(&self, environment: &mut Environment) -> Result<ControlFlow, String> {
+        let message = DataType::stringify_primitives(&self.value.visit(environment)?)?;
+        environment.error_kind = Some("THROW");
+        return Err(error_string_token(&self.token, &message));
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl Display for Throw {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Throw({:?}, {})",
+            self.token,
+            display_expression(&self.value)
+        )
+    }
+}
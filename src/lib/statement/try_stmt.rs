@@ -0,0 +1,67 @@
+use std::{any::Any, fmt::Display};
+
+use crate::{
+    data_type::Record,
+    environment::Environment,
+    interpreter::error_message,
+    statement::{display_statement, ControlFlow, Statement},
+    token::Token,
+};
+
+pub struct Try {
+    pub token: Token,
+    pub body: Box<dyn Statement>,
+    pub name: Token,
+    pub handler: Box<dyn Statement>,
+}
+
+impl Try {
+    // The value of the CATCH variable, an `Error` record with the error
+    // message and where the error came from.
+    fn error_record(environment: &mut Environment, error: &str) -> Record {
+        let message = error_message(error).to_owned();
+        let kind = environment
+            .error_kind
+            .take()
+            .unwrap_or("RUNTIME")
+            .to_owned();
+        return Record {
+            name: "Error".to_owned(),
+            fields: vec![
+                ("message".to_owned(), Box::new(message)),
+                ("kind".to_owned(), Box::new(kind)),
+            ],
+        };
+    }
+}
+
+impl Statement for Try {
+    fn visit(&self, environment: &mut Environment) -> Result<ControlFlow, String> {
+        let error = match self.body.visit(environment) {
+            Err(error) => error,
+            flow => return flow,
+        };
+        let record = Try::error_record(environment, &error);
+        environment.begin_scope();
+        environment.define(self.name.lexeme.clone(), Box::new(record))?;
+        let result = self.handler.visit(environment);
+        environment.end_scope();
+        return result;
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl Display for Try {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Try({}, Catch({:?}, {}))",
+            display_statement(&self.body),
+            self.name,
+            display_statement(&self.handler)
+        )
+    }
+}
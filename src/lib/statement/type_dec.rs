@@ -0,0 +1,32 @@
+use std::{any::Any, fmt::Display};
+
+use crate::{data_type::DataType, environment::Environment, token::Token};
+
+use super::{ControlFlow, Statement};
+
+pub struct TypeDec {
+    pub name: Token,
+    pub fields: Vec<(Token, DataType)>,
+}
+
+impl Statement for TypeDec {
+    fn visit(&self, _environment: &mut Environment) -> Result<ControlFlow, String> {
+        // Record types are only used by the parser, values carry their own fields.
+        return Ok(ControlFlow::Next);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl Display for TypeDec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut result = format!("TypeDec({}\n", self.name);
+        for (name, data_type) in &self.fields {
+            result.push_str(&format!("\tField({}, {:?})\n", name, data_type));
+        }
+        result.push(')');
+        write!(f, "{}", result)
+    }
+}
@@ -1,23 +1,32 @@
 use std::{any::Any, fmt::Display};
 
 use crate::{
+    data_type::Range,
     environment::Environment,
     expression::{display_expression, Expression},
+    interpreter::error_string_token,
     token::Token,
 };
 
-use super::Statement;
+use super::{ControlFlow, Statement};
 
 pub struct Var {
     pub name: Token,
     pub initializer: Box<dyn Expression>,
+    pub range: Option<Range>,
 }
 
 impl Statement for Var {
-    fn visit(&self, environment: &mut Environment) -> Result<(), String> {
+    fn visit(&self, environment: &mut Environment) -> Result<ControlFlow, String> {
         let value = self.initializer.visit(environment)?;
+        if let Some(range) = &self.range {
+            range
+                .check(&self.name.lexeme, &value)
+                .map_err(|error| error_string_token(&self.name, &error))?;
+            environment.define_range(self.name.lexeme.clone(), range.clone());
+        }
         environment.define(self.name.lexeme.clone(), value)?;
-        return Ok(());
+        return Ok(ControlFlow::Next);
     }
 
     fn as_any(&self) -> &dyn Any {
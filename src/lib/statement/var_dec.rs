@@ -2,18 +2,18 @@ use std::{any::Any, fmt::Display};
 
 use crate::environment::Environment;
 
-use super::{var::Var, Statement};
+use super::{var::Var, ControlFlow, Statement};
 
 pub struct VarDec {
     pub variable_declarations: Vec<Var>,
 }
 
 impl Statement for VarDec {
-    fn visit(&self, environment: &mut Environment) -> Result<(), String> {
+    fn visit(&self, environment: &mut Environment) -> Result<ControlFlow, String> {
         for variable_declaration in &self.variable_declarations {
             variable_declaration.visit(environment)?;
         }
-        return Ok(());
+        return Ok(ControlFlow::Next);
     }
 
     fn as_any(&self) -> &dyn Any {
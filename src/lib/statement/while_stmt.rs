@@ -6,7 +6,7 @@ use crate::{
     expression::{display_expression, Expression},
 };
 
-use super::Statement;
+use super::{ControlFlow, Statement};
 
 pub struct While {
     pub condition: Box<dyn Expression>,
@@ -14,18 +14,22 @@ pub struct While {
 }
 
 impl Statement for While {
-    fn visit(&self, environment: &mut Environment) -> Result<(), String> {
+    fn visit(&self, environment: &mut Environment) -> Result<ControlFlow, String> {
         loop {
             if let Some(value) = DataType::any_to_bool(&self.condition.visit(environment)?) {
                 if !value {
                     break;
                 }
-                self.body.visit(environment)?;
+                match self.body.visit(environment)? {
+                    ControlFlow::Break => break,
+                    ControlFlow::Return(value) => return Ok(ControlFlow::Return(value)),
+                    _ => (),
+                }
             } else {
                 return Err("Operand must be a boolean.".to_owned());
             }
         }
-        return Ok(());
+        return Ok(ControlFlow::Next);
     }
 
     fn as_any(&self) -> &dyn Any {
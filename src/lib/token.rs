@@ -1,5 +1,5 @@
 use super::token_type::*;
-use std::fmt;
+use std::{fmt, rc::Rc};
 
 #[derive(Clone, Debug)]
 pub struct Token {
@@ -7,6 +7,8 @@ pub struct Token {
     pub lexeme: String,
     pub line: usize,
     pub column: usize,
+    // Set only for tokens that come from an INCLUDE'd file.
+    pub file: Option<Rc<str>>,
 }
 
 impl Token {
@@ -16,8 +18,18 @@ impl Token {
             lexeme,
             line,
             column,
+            file: None,
         }
     }
+
+    pub fn location(&self) -> String {
+        let line = self.line + 1;
+        let column = self.column + 1;
+        return match &self.file {
+            Some(file) => format!("{file}:line-{line}:column-{column}"),
+            None => format!("line-{line}:column-{column}"),
+        };
+    }
 }
 
 impl fmt::Display for Token {
@@ -4,6 +4,8 @@ use std::fmt;
 pub enum TokenType {
     SymLeftParenthesis,
     SymRightParenthesis,
+    SymLeftBracket,
+    SymRightBracket,
 
     SymComma,
     SymAssignment,
@@ -14,6 +16,16 @@ pub enum TokenType {
     SymStar,
     SymForwardSlash,
     SymPercent,
+    SymDot,
+    SymPower,
+    SymPlusAssignment,
+    SymMinusAssignment,
+    SymStarAssignment,
+    SymForwardSlashAssignment,
+    SymPercentAssignment,
+    SymAmpersandAssignment,
+    SymIncrement,
+    SymDecrement,
 
     SymGreater,
     SymLesser,
@@ -46,6 +58,35 @@ pub enum TokenType {
     RkwIf,
     RkwElse,
     RkwWhile,
+    RkwFor,
+    RkwTo,
+    RkwStep,
+    RkwBreak,
+    RkwContinue,
+    RkwSwitch,
+    RkwCase,
+    RkwDefault,
+    RkwFunction,
+    RkwSub,
+    RkwReturn,
+    RkwString,
+    RkwConst,
+    RkwInclude,
+    RkwType,
+    RkwEnd,
+    RkwBand,
+    RkwBor,
+    RkwBxor,
+    RkwBnot,
+    RkwShl,
+    RkwShr,
+    RkwIif,
+    RkwRange,
+    RkwTry,
+    RkwCatch,
+    RkwThrow,
+    RkwAssert,
+    RkwTest,
     Eol,
     Eof,
 }
@@ -76,6 +117,35 @@ impl TokenType {
                 | TokenType::RkwIf
                 | TokenType::RkwElse
                 | TokenType::RkwWhile
+                | TokenType::RkwFor
+                | TokenType::RkwTo
+                | TokenType::RkwStep
+                | TokenType::RkwBreak
+                | TokenType::RkwContinue
+                | TokenType::RkwSwitch
+                | TokenType::RkwCase
+                | TokenType::RkwDefault
+                | TokenType::RkwFunction
+                | TokenType::RkwSub
+                | TokenType::RkwReturn
+                | TokenType::RkwString
+                | TokenType::RkwConst
+                | TokenType::RkwInclude
+                | TokenType::RkwType
+                | TokenType::RkwEnd
+                | TokenType::RkwBand
+                | TokenType::RkwBor
+                | TokenType::RkwBxor
+                | TokenType::RkwBnot
+                | TokenType::RkwShl
+                | TokenType::RkwShr
+                | TokenType::RkwIif
+                | TokenType::RkwRange
+                | TokenType::RkwTry
+                | TokenType::RkwCatch
+                | TokenType::RkwThrow
+                | TokenType::RkwAssert
+                | TokenType::RkwTest
         );
     }
 }
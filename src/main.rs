@@ -1,6 +1,7 @@
+use cfpl::interpreter::Options;
 use std::{env, path::Path};
 
-static HELP_MESSAGE: &str = "Usage: cfpl <type> <type parameter>
+static HELP_MESSAGE: &str = "Usage: cfpl <type> <type parameter> [options]
                                     \n\ttype:
                                     \n\t   --file or -f
                                     \n\t   type parameter:
@@ -8,6 +9,14 @@ static HELP_MESSAGE: &str = "Usage: cfpl <type> <type parameter>
                                     \n\t   --string or -s
                                     \n\t   type parameter:
                                     \n\t      <string source code> (i.e. \"VAR ab as INT\\nSTART\\nOUTPUT: ab\\nSTOP\")
+                                    \n\t   test
+                                    \n\t   type parameter:
+                                    \n\t      <filename with path>, runs the TEST sections of the file
+                                    \n\toptions:
+                                    \n\t   --interactive or -i
+                                    \n\t      re-prompt instead of aborting on an invalid INPUT value
+                                    \n\t   --max-call-depth or -d <depth>
+                                    \n\t      maximum number of nested FUNCTION/SUB calls (default 1000)
                                     ";
 
 enum ArgumentType {
@@ -18,21 +27,46 @@ enum ArgumentType {
 struct Config<'a> {
     argument_type: ArgumentType,
     argument_type_parameter: &'a str,
+    options: Options,
 }
 
 impl Config<'_> {
     fn new(argument: &[String]) -> Result<Config, String> {
-        if argument.len() != 3 {
+        if argument.len() < 3 {
             return Err(HELP_MESSAGE.to_owned());
         }
 
         let mut config = Config {
             argument_type: ArgumentType::File,
             argument_type_parameter: &argument[2],
+            options: Options::default(),
         };
 
+        let mut options = argument[3..].iter();
+        while let Some(option) = options.next() {
+            match option.as_str() {
+                "--interactive" | "-i" => config.options.interactive = true,
+                "--max-call-depth" | "-d" => {
+                    config.options.max_call_depth =
+                        match options.next().and_then(|depth| depth.parse::<usize>().ok()) {
+                            Some(depth) => depth,
+                            None => {
+                                return Err(format!(
+                                    "Expected a call depth after {}\n{}",
+                                    option, HELP_MESSAGE
+                                ));
+                            }
+                        }
+                }
+                _ => {
+                    return Err(format!("Invalid option: {}\n{}", option, HELP_MESSAGE));
+                }
+            }
+        }
+
+        config.options.run_tests = argument[1] == "test";
         return match argument[1].as_str() {
-            "--file" | "-f" => {
+            "--file" | "-f" | "test" => {
                 if let Some(extension) = Path::new(config.argument_type_parameter)
                     .extension()
                     .and_then(|extension| extension.to_str())
@@ -69,8 +103,10 @@ fn main() {
     let config = Config::new(&args);
     let is_success = match config {
         Ok(config) => match config.argument_type {
-            ArgumentType::File => cfpl::file(config.argument_type_parameter),
-            ArgumentType::String => cfpl::execute(config.argument_type_parameter.to_owned()),
+            ArgumentType::File => cfpl::file(config.argument_type_parameter, &config.options),
+            ArgumentType::String => {
+                cfpl::execute(config.argument_type_parameter.to_owned(), &config.options)
+            }
         },
         Err(error) => {
             eprint!("{}", error);
@@ -1,4 +1,4 @@
-use cfpl::file;
+use cfpl::{file, interpreter::Options};
 use std::fs;
 
 pub fn no_input(expected: bool, path: &str) {
@@ -10,7 +10,7 @@ pub fn no_input(expected: bool, path: &str) {
         let path = path.unwrap();
         let path = path.path().to_str().unwrap().to_owned();
         println!("Test file full path: {}", &path);
-        let result = file(&path);
+        let result = file(&path, &Options::default());
         assert_eq!(expected, result);
         println!();
     }